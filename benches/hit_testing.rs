@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::spatial::SpatialIndex;
+
+// An inventory grid of 40x40 slots, 100 to a row.
+fn inventory(count: usize) -> Vec<Option<Rect>> {
+    (0..count)
+        .map(|slot| Some(Rect::new((slot % 100) as f32 * 44.0, (slot / 100) as f32 * 44.0, 40.0, 40.0)))
+        .collect()
+}
+
+// Points spread over the grid, some on slots and some in the gaps between them.
+fn pointers(count: usize) -> Vec<Vec2> {
+    let rows = (count / 100).max(1) as f32;
+    (0..64)
+        .map(|point| vec2((point * 37 % 100) as f32 * 44.0 + 21.0, (point % 13) as f32 / 13.0 * rows * 44.0 + (point % 3) as f32 * 20.0))
+        .collect()
+}
+
+fn linear_find(rects: &[Option<Rect>], point: Vec2) -> Option<usize> {
+    rects.iter().rposition(|rect| rect.is_some_and(|rect| rect.contains(point)))
+}
+
+fn hit_testing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("element under pointer");
+    for count in [1_000, 10_000] {
+        let rects = inventory(count);
+        let points = pointers(count);
+        let index = SpatialIndex::new(rects.clone());
+
+        group.bench_with_input(BenchmarkId::new("linear", count), &points, |b, points| {
+            b.iter(|| points.iter().map(|point| linear_find(&rects, *point)).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("grid", count), &points, |b, points| {
+            b.iter(|| points.iter().map(|point| index.find(*point)).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("grid build", count), &rects, |b, rects| {
+            b.iter(|| SpatialIndex::new(black_box(rects.clone())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hit_testing);
+criterion_main!(benches);
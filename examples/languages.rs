@@ -0,0 +1,31 @@
+use macroquad::prelude::*;
+use Simples_menu::i18n::{self, StringTable};
+use Simples_menu::{Button, Menu, PositionType};
+
+#[macroquad::main("Languages")]
+async fn main() {
+    i18n::add_language("en", StringTable::load("examples/lang/en.lang").unwrap());
+    i18n::add_language("pt", StringTable::load("examples/lang/pt.lang").unwrap());
+
+    let mut menu = Menu::new("Main menu".to_string(), Vec2{ x: 50.0, y: 50.0 });
+    menu.title.key = Some("menu.title".to_string());
+
+    let start = menu.add_element(Button::new("Start game".to_string(), PositionType::TopLeft, Vec2{ x: 0.0, y: 0.0 }, None));
+    start.edit().title.key = Some("menu.start".to_string());
+    let language = menu.add_element(Button::new("Language".to_string(), PositionType::TopLeft, Vec2{ x: 0.0, y: 30.0 }, None));
+    language.edit().title.key = Some("menu.language".to_string());
+    let quit = menu.add_element(Button::new("Quit".to_string(), PositionType::TopLeft, Vec2{ x: 0.0, y: 60.0 }, None));
+    quit.edit().title.key = Some("menu.quit".to_string());
+
+    loop {
+        menu.update();
+
+        if language.read().has_been_pressed {
+            let next = if i18n::language() == "en" { "pt" } else { "en" };
+            i18n::set_language(next);
+        }
+
+        menu.draw();
+        next_frame().await
+    }
+}
@@ -0,0 +1,24 @@
+use macroquad::prelude::*;
+use Simples_menu::loader::load_file;
+
+#[macroquad::main("Loader")]
+async fn main() {
+    let layout = match load_file("examples/menu1.ron") {
+        Ok(layout) => layout,
+        Err(error) => panic!("examples/menu1.ron: {}", error),
+    };
+    let mut menu1 = layout.menu;
+    let buttan1 = layout.elements["buttan1"].as_button().unwrap();
+    let label1 = layout.elements["label1"].as_text_label().unwrap();
+    let check_box = layout.elements["check_box"].as_check_box().unwrap();
+
+    loop {
+        menu1.update();
+
+        label1.edit().title.name = get_fps().to_string();
+        if check_box.read().is_checked {buttan1.edit().position.x += 0.1}
+
+        menu1.draw();
+        next_frame().await
+    }
+}
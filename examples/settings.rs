@@ -0,0 +1,50 @@
+use macroquad::prelude::*;
+use Simples_menu::{MenuChoice, MenuForm};
+
+#[derive(MenuChoice)]
+enum Quality {
+    Low,
+    Medium,
+    High,
+    #[menu(label = "Very high")]
+    VeryHigh,
+}
+
+#[derive(MenuForm)]
+struct Settings {
+    vsync: bool,
+    #[menu(min = 0.0, max = 1.0)]
+    music_volume: f32,
+    #[menu(label = "Field of view", min = 60, max = 120)]
+    fov: u32,
+    max_fps: u32,
+    player_name: String,
+    quality: Quality,
+    #[menu(skip)]
+    launches: u32,
+}
+
+#[macroquad::main("Settings")]
+async fn main() {
+    let mut settings = Settings{
+        vsync: true,
+        music_volume: 0.8,
+        fov: 90,
+        max_fps: 144,
+        player_name: "Player".to_string(),
+        quality: Quality::High,
+        launches: 0,
+    };
+    let mut form = settings.to_menu("Options", Vec2{ x: 50.0, y: 50.0 });
+
+    loop {
+        form.menu.update();
+        settings.read_menu(&form);
+
+        draw_text(&format!("{} plays at {:.2} volume", settings.player_name, settings.music_volume), 20.0, 20.0, 20.0, WHITE);
+        settings.launches += 1;
+
+        form.menu.draw();
+        next_frame().await
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+use macroquad::prelude::*;
+use Simples_menu::terminal::Terminal;
+use Simples_menu::{Button, CheckBox, Menu, PositionType, TextLabel};
+
+// Run with `cargo run --example terminal --features terminal`, and quit with Escape or the Quit button.
+fn main() -> std::io::Result<()> {
+    // Elements measure their text when built, so the terminal has to be started first.
+    let mut terminal = Terminal::start()?;
+
+    let mut menu = Menu::new("Server".to_string(), Vec2{ x: 16.0, y: 32.0 });
+    menu.add_owned_element(None, TextLabel::new("Accept new players".to_string(), PositionType::TopLeft, Vec2::ZERO));
+    let open = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2{ x: 160.0, y: 0.0 }, Vec2{ x: 16.0, y: 16.0 }));
+    let restart = menu.add_element(Button::new("Restart".to_string(), PositionType::TopLeft, Vec2{ x: 0.0, y: 32.0 }, None));
+    let quit = menu.add_element(Button::new("Quit".to_string(), PositionType::TopLeft, Vec2{ x: 96.0, y: 32.0 }, None));
+    let status = menu.add_element(TextLabel::new(String::new(), PositionType::TopLeft, Vec2{ x: 0.0, y: 80.0 }));
+    terminal.attach(&mut menu);
+
+    let mut restarts = 0;
+    loop {
+        let keys = terminal.read_input()?;
+        if keys.pressed(KeyCode::Escape) {
+            break;
+        }
+
+        menu.update();
+        if quit.read().has_been_pressed {
+            break;
+        }
+        if restart.read().has_been_pressed {
+            restarts += 1;
+        }
+        let text = format!("{} restarts, {}", restarts, if open.read().is_checked { "open" } else { "closed" });
+        if status.read().title.name != text {
+            status.edit().title.name = text;
+        }
+
+        terminal.clear();
+        menu.draw();
+        Simples_menu::tooltip::draw_overlay();
+        terminal.present()?;
+        std::thread::sleep(Duration::from_millis(16));
+    }
+
+    Ok(())
+}
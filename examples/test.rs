@@ -1,29 +1,45 @@
+use std::cell::Cell;
+use std::rc::Rc;
 use macroquad::prelude::*;
-use Simples_menu::{Button, CheckBox, Menu, TextLabel};
+use Simples_menu::{debug, menu, set_ui_scale, tooltip, ui_scale, Button, CheckBox, Menu, TextLabel};
 use Simples_menu::PositionType::{Center, TopLeft};
+use Simples_menu::animation::{Easing, Transition};
+use Simples_menu::inspector::Inspector;
 
 #[macroquad::main("Test")]
 async fn main() {
-    let mut menu1 = Menu::new("Menu 1".to_string(), Vec2{ x: 50.0, y: 50.0 });
-    //menu1.size = Some(Vec2 { x: 220.0, y: 80.0 });
-    let buttan1 = menu1.add_element(Button::new("Buttom 1".to_string(),TopLeft,Vec2{ x: 0.0, y: 120.0 }, None));
-    let buttan2 = menu1.add_element(Button::new("Buttom 2".to_string(),TopLeft,Vec2{ x: 0.0, y: 0.0 }, None));
-    let label1 = menu1.add_element(TextLabel::new("Teste de legenda: ".to_string(), TopLeft, Vec2{ x: 30.0, y: 50.0 }));
-
-    let mut menu2 = Menu::new("Menu 2".to_string(), Vec2{ x: 250.0, y: 260.0 });
-    //menu2.color = BLUE;
-    let buttan3 = menu2.add_element(Button::new("Buttom 3".to_string(), TopLeft, Vec2{ x: 0.0, y: 30.0 }, None));
-    let buttan4 = menu2.add_element(Button::new("Buttom 4".to_string(), TopLeft,Vec2{ x: 20.0, y: 0.0 }, None));
-    let check_box = menu2.add_element(CheckBox::new(Center, Vec2{ x: 20.0, y: 90.0 }, Vec2{ x: 20.0, y: 20.0 }));
-
-    let mut menu3 = Menu::new("Menu 3".to_string(), Vec2{ x: 500.0, y: 260.0 });
-    let buttan5 = menu3.add_element(Button::new("Buttom 5".to_string(), Center, Vec2{ x: 0.0, y: 0.0 }, None));
+    menu! {
+        let menu1 = Menu("Menu 1", Vec2{ x: 50.0, y: 50.0 }) {
+            let buttan1 = Button("Buttom 1", TopLeft, Vec2{ x: 0.0, y: 120.0 }, None);
+            Button("Buttom 2", TopLeft, Vec2{ x: 0.0, y: 0.0 }, None);
+            let label1 = TextLabel("Teste de legenda: ", TopLeft, Vec2{ x: 30.0, y: 50.0 });
+        }
+        let menu2 = Menu("Menu 2", Vec2{ x: 250.0, y: 260.0 }) {
+            Button("Buttom 3", TopLeft, Vec2{ x: 0.0, y: 30.0 }, None);
+            Button("Buttom 4", TopLeft, Vec2{ x: 20.0, y: 0.0 }, None);
+            let check_box = CheckBox(Center, Vec2{ x: 20.0, y: 90.0 }, Vec2{ x: 20.0, y: 20.0 });
+        }
+        let menu3 = Menu("Menu 3", Vec2{ x: 500.0, y: 260.0 }) {
+            Button("Buttom 5", Center, Vec2{ x: 0.0, y: 0.0 }, None);
+        }
+    }
 
+    // Moves buttan1 while the check box is checked, pressing M toggles it from the game side.
+    let slide_button = Rc::new(Cell::new(false));
+    check_box.edit().bind(slide_button.clone());
+    check_box.set_tooltip("Slide Buttom 1 to the right");
+    // F4 shows the inspector.
+    let mut inspector = Inspector::new(Vec2{ x: 560.0, y: 20.0 });
+    inspector.menu.visible = false;
 
     loop {
         menu1.update();
         menu2.update();
         menu3.update();
+        inspector.update(&mut [&mut menu1, &mut menu2, &mut menu3]);
+        if let Some(snippet) = inspector.snippet() {
+            println!("{snippet}");
+        }
 
         label1.edit().title.name = get_fps().to_string();
         if is_key_released(KeyCode::O) {
@@ -37,11 +53,27 @@ async fn main() {
             }
         }
 
-        if check_box.read().is_checked {buttan1.edit().position.x += 0.1}
+        if slide_button.get() {buttan1.edit().position.x += 0.1}
+        if is_key_pressed(KeyCode::M) {slide_button.set(!slide_button.get())}
+        if is_key_pressed(KeyCode::Equal) {set_ui_scale(ui_scale() + 0.25)}
+        if is_key_pressed(KeyCode::Minus) {set_ui_scale((ui_scale() - 0.25).max(0.5))}
+        if is_key_pressed(KeyCode::F3) {debug::set_overlay(!debug::overlay())}
+        if is_key_pressed(KeyCode::F4) {inspector.menu.visible = !inspector.menu.visible}
+
+        if is_key_pressed(KeyCode::H) {
+            let transition = Transition::SlideFade { offset: Vec2{ x: 0.0, y: 40.0 }, duration: 0.3, easing: Easing::QuadOut };
+            if menu3.visible {
+                menu3.hide(transition);
+            } else {
+                menu3.show(transition);
+            }
+        }
 
         menu3.draw();
         menu2.draw();
         menu1.draw();
+        inspector.draw();
+        tooltip::draw_overlay();
         next_frame().await
     }
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
+
+/// The roles a field can play for `Interactive`, with the field name used when none is marked.
+const ROLES: [(&str, &str); 9] = [
+    ("position", "position"),
+    ("size", "size"),
+    ("color", "color"),
+    ("tint", "visible_color"),
+    ("pressed", "is_pressed"),
+    ("clicked", "has_been_pressed"),
+    ("enabled", "enabled"),
+    ("activation", "activation"),
+    ("disabled_reason", "disabled_reason"),
+];
+
+/// `pressed`, `clicked`, `enabled`, `activation` and `disabled_reason` are optional, every other role needs a field.
+fn is_required(role: &str) -> bool {
+    !matches!(role, "pressed" | "clicked" | "enabled" | "activation" | "disabled_reason")
+}
+
+pub fn derive_clickable(ast: DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(name.span(), "Clickable can only be derived for structs with named fields")),
+        },
+        _ => return Err(Error::new(name.span(), "Clickable can only be derived for structs")),
+    };
+
+    // Fields marked with `#[clickable(role)]` win over the conventional names.
+    let mut marked: Vec<(String, Ident)> = vec![];
+    for field in fields {
+        let ident = field.ident.clone().expect("named fields have an ident");
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("clickable")) {
+            attr.parse_nested_meta(|meta| {
+                let Some(role) = ROLES.iter().map(|(role, _)| *role).find(|role| meta.path.is_ident(role)) else {
+                    return Err(meta.error("expected `position`, `size`, `color`, `tint`, `pressed`, `clicked`, `enabled`, `activation` or `disabled_reason`"));
+                };
+                if marked.iter().any(|(marked_role, _)| marked_role == role) {
+                    return Err(meta.error(format!("more than one field is marked as `{}`", role)));
+                }
+                marked.push((role.to_string(), ident.clone()));
+                Ok(())
+            })?;
+        }
+    }
+
+    let mut resolved = vec![];
+    for (role, default_name) in ROLES {
+        let field = marked.iter()
+            .find(|(marked_role, _)| marked_role == role)
+            .map(|(_, ident)| ident.clone())
+            .or_else(|| {
+                fields.iter()
+                    .filter_map(|field| field.ident.clone())
+                    .find(|ident| ident == default_name)
+            });
+        if field.is_none() && is_required(role) {
+            return Err(Error::new(name.span(), format!(
+                "struct `{}` has no `{}` field, mark the field to use with `#[clickable({})]`",
+                name, default_name, role,
+            )));
+        }
+        resolved.push(field);
+    }
+
+    let [position, size, color, tint, pressed, clicked, enabled, activation, disabled_reason]: [Option<Ident>; 9] = resolved.try_into().expect("one field per role");
+    let (position, size, color, tint) = (position.unwrap(), size.unwrap(), color.unwrap(), tint.unwrap());
+
+    let set_pressed = pressed.map(|field| quote! {
+        fn set_pressed(&mut self, is_pressed: bool) {
+            self.#field = is_pressed;
+        }
+    });
+    let set_clicked = clicked.map(|field| quote! {
+        fn set_clicked(&mut self, has_been_pressed: bool) {
+            self.#field = has_been_pressed;
+        }
+    });
+
+    let is_enabled = enabled.map(|field| quote! {
+        fn is_enabled(&self) -> bool {
+            self.#field
+        }
+    });
+
+    let activation = activation.map(|field| quote! {
+        fn activation(&self) -> ::Simples_menu::Activation {
+            self.#field
+        }
+    });
+
+    let disabled_reason = disabled_reason.map(|field| quote! {
+        fn disabled_reason(&self) -> ::std::option::Option<::std::string::String> {
+            self.#field.clone()
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::Simples_menu::Interactive for #name #ty_generics #where_clause {
+            fn hit_rect(&self, menu_position: ::Simples_menu::macroquad::math::Vec2) -> ::Simples_menu::macroquad::math::Rect {
+                ::Simples_menu::macroquad::math::Rect::new(
+                    self.#position.x + menu_position.x,
+                    self.#position.y + menu_position.y,
+                    self.#size.x,
+                    self.#size.y,
+                )
+            }
+
+            fn base_color(&self) -> ::Simples_menu::macroquad::color::Color {
+                self.#color
+            }
+
+            fn set_tint(&mut self, color: ::Simples_menu::macroquad::color::Color) {
+                self.#tint = color;
+            }
+
+            #set_pressed
+            #set_clicked
+            #is_enabled
+            #activation
+            #disabled_reason
+        }
+    })
+}
@@ -0,0 +1,306 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, LitStr, Result, Type};
+
+const INTEGERS: [&str; 12] = ["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+const FLOATS: [&str; 2] = ["f32", "f64"];
+
+/// What a field of a `MenuForm` struct is edited with.
+enum Widget {
+    CheckBox,
+    Slider(Box<SliderRange>),
+    NumberInput,
+    TextInput,
+    Dropdown,
+}
+
+/// The range a numeric field with `min` and `max` is slid over.
+struct SliderRange {
+    min: Expr,
+    max: Expr,
+    step: Option<Expr>,
+    integer: bool,
+}
+
+/// The `#[menu(...)]` options of a field or variant.
+#[derive(Default)]
+struct MenuAttributes {
+    label: Option<LitStr>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    step: Option<Expr>,
+    skip: bool,
+}
+
+fn parse_attributes(attrs: &[Attribute]) -> Result<MenuAttributes> {
+    let mut options = MenuAttributes::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("menu")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                options.label = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("min") {
+                options.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                options.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("step") {
+                options.step = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else {
+                return Err(meta.error("expected `label`, `min`, `max`, `step` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// `music_volume` -> `Music volume`
+fn label_from_ident(ident: &Ident) -> String {
+    let name = ident.to_string().replace('_', " ");
+    let mut characters = name.trim().chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => name,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            Some(path.path.segments[0].ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn widget_for(ty: &Type, options: &mut MenuAttributes) -> Result<Widget> {
+    let name = type_name(ty).unwrap_or_default();
+    let is_number = INTEGERS.contains(&name.as_str()) || FLOATS.contains(&name.as_str());
+
+    if !is_number && (options.min.is_some() || options.max.is_some() || options.step.is_some()) {
+        return Err(Error::new(ty.span(), "`min`, `max` and `step` can only be used on numeric fields"));
+    }
+
+    Ok(match name.as_str() {
+        "bool" => Widget::CheckBox,
+        "String" => Widget::TextInput,
+        _ if is_number => match (options.min.take(), options.max.take()) {
+            (Some(min), Some(max)) => Widget::Slider(Box::new(SliderRange {
+                min,
+                max,
+                step: options.step.take(),
+                integer: INTEGERS.contains(&name.as_str()),
+            })),
+            (None, None) if options.step.is_none() => Widget::NumberInput,
+            _ => return Err(Error::new(ty.span(), "a slider needs both `min` and `max`")),
+        },
+        _ => Widget::Dropdown,
+    })
+}
+
+pub fn derive_menu_form(ast: DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let visibility = &ast.vis;
+    let form_name = format_ident!("{}Form", name);
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match &ast.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(name.span(), "MenuForm can only be derived for structs with named fields")),
+        },
+        _ => return Err(Error::new(name.span(), "MenuForm can only be derived for structs")),
+    };
+
+    let mut form_fields = vec![];
+    let mut build = vec![];
+    let mut read = vec![];
+    let mut handles = vec![];
+    let mut row = 0usize;
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        let mut options = parse_attributes(&field.attrs)?;
+        if options.skip {
+            continue;
+        }
+        if ident == "menu" {
+            return Err(Error::new(ident.span(), "a field named `menu` would clash with the generated `menu` field of the form"));
+        }
+
+        let ty = &field.ty;
+        let label = options.label.take()
+            .map(|label| label.value())
+            .unwrap_or_else(|| label_from_ident(ident));
+        let key = ident.to_string();
+        let y = row as f32 * 30.0;
+        let label_y = y + 15.0;
+        row += 1;
+
+        build.push(quote! {
+            menu.add_owned_element(::std::option::Option::None, ::Simples_menu::TextLabel::new(
+                ::std::string::String::from(#label), ::Simples_menu::PositionType::TopLeft, ::Simples_menu::macroquad::math::vec2(0.0, #label_y)));
+        });
+
+        let (element_type, element, write_back) = match widget_for(ty, &mut options)? {
+            Widget::CheckBox => (
+                quote! { ::Simples_menu::CheckBox },
+                quote! {{
+                    let mut element = ::Simples_menu::CheckBox::new(::Simples_menu::PositionType::TopLeft,
+                        ::Simples_menu::macroquad::math::vec2(150.0, #y), ::Simples_menu::macroquad::math::vec2(20.0, 20.0));
+                    element.is_checked = self.#ident;
+                    element
+                }},
+                quote! { self.#ident = form.#ident.read().is_checked; },
+            ),
+            Widget::Slider(range) => {
+                let SliderRange { min, max, step, integer } = *range;
+                let step = match step {
+                    Some(step) => quote! { ::std::option::Option::Some((#step) as f32) },
+                    None if integer => quote! { ::std::option::Option::Some(1.0) },
+                    None => quote! { ::std::option::Option::None },
+                };
+                let value = if integer {
+                    quote! { form.#ident.read().value.round() as #ty }
+                } else {
+                    quote! { form.#ident.read().value as #ty }
+                };
+                (
+                    quote! { ::Simples_menu::Slider },
+                    quote! {{
+                        let mut element = ::Simples_menu::Slider::new(::Simples_menu::PositionType::TopLeft,
+                            ::Simples_menu::macroquad::math::vec2(150.0, #y), ::Simples_menu::macroquad::math::vec2(150.0, 20.0),
+                            (#min) as f32, (#max) as f32, self.#ident as f32);
+                        element.step = #step;
+                        element
+                    }},
+                    quote! { self.#ident = #value; },
+                )
+            }
+            Widget::NumberInput => (
+                quote! { ::Simples_menu::TextInput },
+                quote! {{
+                    let mut element = ::Simples_menu::TextInput::new(::Simples_menu::PositionType::TopLeft,
+                        ::Simples_menu::macroquad::math::vec2(150.0, #y), ::Simples_menu::macroquad::math::vec2(150.0, 20.0));
+                    element.numeric = true;
+                    element.text = self.#ident.to_string();
+                    element
+                }},
+                // Leave the field untouched while the text isn't a valid number.
+                quote! {
+                    if let ::std::result::Result::Ok(value) = form.#ident.read().text.trim().parse::<#ty>() {
+                        self.#ident = value;
+                    }
+                },
+            ),
+            Widget::TextInput => (
+                quote! { ::Simples_menu::TextInput },
+                quote! {{
+                    let mut element = ::Simples_menu::TextInput::new(::Simples_menu::PositionType::TopLeft,
+                        ::Simples_menu::macroquad::math::vec2(150.0, #y), ::Simples_menu::macroquad::math::vec2(150.0, 20.0));
+                    element.text = ::std::clone::Clone::clone(&self.#ident);
+                    element
+                }},
+                quote! { self.#ident = ::std::clone::Clone::clone(&form.#ident.read().text); },
+            ),
+            Widget::Dropdown => (
+                quote! { ::Simples_menu::Dropdown },
+                quote_spanned! {ty.span()=> {
+                    let current = <#ty as ::Simples_menu::MenuChoice>::label(&self.#ident);
+                    let options: ::std::vec::Vec<::std::string::String> = <#ty as ::Simples_menu::MenuChoice>::choices()
+                        .iter()
+                        .map(<#ty as ::Simples_menu::MenuChoice>::label)
+                        .collect();
+                    let mut element = ::Simples_menu::Dropdown::new(::Simples_menu::PositionType::TopLeft,
+                        ::Simples_menu::macroquad::math::vec2(150.0, #y), ::std::option::Option::None, options);
+                    element.selected = element.options.iter().position(|option| *option == current).unwrap_or(0);
+                    element
+                }},
+                quote_spanned! {ty.span()=>
+                    if let ::std::option::Option::Some(choice) = <#ty as ::Simples_menu::MenuChoice>::choices()
+                        .into_iter()
+                        .nth(form.#ident.read().selected)
+                    {
+                        self.#ident = choice;
+                    }
+                },
+            ),
+        };
+
+        form_fields.push(quote! { pub #ident: ::Simples_menu::Element<#element_type> });
+        build.push(quote! { let #ident = menu.add_named_element(#key, #element); });
+        read.push(write_back);
+        handles.push(ident.clone());
+    }
+
+    let form_doc = format!("The menu generated for `{}` by `#[derive(MenuForm)]`.", name);
+    Ok(quote! {
+        #[doc = #form_doc]
+        #visibility struct #form_name {
+            pub menu: ::Simples_menu::Menu,
+            #(#form_fields,)*
+        }
+
+        impl #impl_generics ::Simples_menu::MenuForm for #name #ty_generics #where_clause {
+            type Form = #form_name;
+
+            fn to_menu(&self, title: &str, position: ::Simples_menu::macroquad::math::Vec2) -> #form_name {
+                let mut menu = ::Simples_menu::Menu::new(::std::string::String::from(title), position);
+                #(#build)*
+                #form_name { menu, #(#handles),* }
+            }
+
+            fn read_menu(&mut self, form: &#form_name) {
+                #(#read)*
+            }
+        }
+    })
+}
+
+pub fn derive_menu_choice(ast: DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => return Err(Error::new(name.span(), "MenuChoice can only be derived for enums")),
+    };
+    if variants.is_empty() {
+        return Err(Error::new(name.span(), "MenuChoice needs at least one variant"));
+    }
+
+    let mut choices = vec![];
+    let mut labels = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(variant.fields.span(), "MenuChoice variants can't hold data"));
+        }
+        let options = parse_attributes(&variant.attrs)?;
+        if options.min.is_some() || options.max.is_some() || options.step.is_some() || options.skip {
+            return Err(Error::new(variant.ident.span(), "only `label` can be used on MenuChoice variants"));
+        }
+
+        let ident = &variant.ident;
+        let label = options.label
+            .map(|label| label.value())
+            .unwrap_or_else(|| ident.to_string());
+        choices.push(quote! { #name::#ident });
+        labels.push(quote! { #name::#ident => ::std::string::String::from(#label) });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::Simples_menu::MenuChoice for #name #ty_generics #where_clause {
+            fn choices() -> ::std::vec::Vec<Self> {
+                ::std::vec![#(#choices),*]
+            }
+
+            fn label(&self) -> ::std::string::String {
+                match self {
+                    #(#labels,)*
+                }
+            }
+        }
+    })
+}
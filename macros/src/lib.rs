@@ -1,40 +1,79 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
 
-#[proc_macro_derive(BoundingRect)]
+mod clickable;
+mod form;
+mod menu;
+
+#[proc_macro_derive(BoundingRect, attributes(bounding))]
 pub fn bounding_rect(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree.
     let ast = parse_macro_input!(input as DeriveInput);
 
+    derive_bounding_rect(ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+fn derive_bounding_rect(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     // Get the name of the struct.
     let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
 
     // Get the fields of the struct.
     let fields = match ast.data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => &fields.named,
-            _ => unimplemented!(),
+            _ => return Err(Error::new(name.span(), "BoundingRect can only be derived for structs with named fields")),
         },
-        _ => unimplemented!(),
+        Data::Enum(ref data) => return Err(Error::new(data.enum_token.span, "BoundingRect can't be derived for enums")),
+        Data::Union(ref data) => return Err(Error::new(data.union_token.span, "BoundingRect can't be derived for unions")),
     };
 
+    // The fields used can be renamed with `#[bounding(pos = "origin")]`.
+    let mut visible = Ident::new("visible", Span::call_site());
+    let mut position = Ident::new("position", Span::call_site());
+    let mut size = Ident::new("size", Span::call_site());
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("bounding")) {
+        attr.parse_nested_meta(|meta| {
+            let target = if meta.path.is_ident("visible") {
+                &mut visible
+            } else if meta.path.is_ident("pos") || meta.path.is_ident("position") {
+                &mut position
+            } else if meta.path.is_ident("size") {
+                &mut size
+            } else {
+                return Err(meta.error("expected `visible`, `pos` or `size`"));
+            };
+            let field: LitStr = meta.value()?.parse()?;
+            *target = field.parse()?;
+            Ok(())
+        })?;
+    }
+
     // Check that the struct has the required fields.
-    let has_visible = fields.iter().any(|field| field.ident.as_ref().unwrap() == "visible");
-    let has_position = fields.iter().any(|field| field.ident.as_ref().unwrap() == "position");
-    let has_size = fields.iter().any(|field| field.ident.as_ref().unwrap() == "size");
-    if !(has_visible && has_position && has_size) {
-        panic!("Struct {} does not have the required fields", name);
+    let missing: Vec<String> = [&visible, &position, &size].iter()
+        .filter(|required| !fields.iter().any(|field| field.ident.as_ref() == Some(**required)))
+        .map(|required| format!("`{}`", required))
+        .collect();
+    if !missing.is_empty() {
+        return Err(Error::new(name.span(), format!(
+            "struct `{}` has no {} field, rename the fields used with `#[bounding(visible = \"..\", pos = \"..\", size = \"..\")]`",
+            name,
+            missing.join(" or "),
+        )));
     }
 
     // Generate the code for the bounding_rect method.
     let gen = quote! {
-        impl #name {
+        impl #impl_generics #name #ty_generics #where_clause {
             fn bounding_rect(&self) -> Option<Rect> {
-                if self.visible {
-                    Some(Rect::new(self.position.x, self.position.y, self.size.x, self.size.y))
+                if self.#visible {
+                    Some(Rect::new(self.#position.x, self.#position.y, self.#size.x, self.#size.y))
                 } else {
                     None
                 }
@@ -43,5 +82,69 @@ pub fn bounding_rect(input: TokenStream) -> TokenStream {
     };
 
     // Return the generated code as a TokenStream.
-    gen.into()
+    Ok(gen)
+}
+
+/// Build menus from a tree of elements, binding each `let` to a local variable.
+///
+/// ```ignore
+/// menu! {
+///     let menu1 = Menu("Menu 1", vec2(50.0, 50.0)) [color: BLUE] {
+///         let start = Button("Start", TopLeft, vec2(0.0, 0.0), None);
+///         TextLabel("Version 1.0", TopLeft, vec2(0.0, 40.0));
+///     }
+/// }
+/// ```
+///
+/// Each element is built with `Type::new(args)`, string literals are turned into `String`s and
+/// the optional `[field: value]` list is assigned right after. Top level menus are bound to the
+/// given name, child elements to the `Element` returned by their parent's `add_element`.
+#[proc_macro]
+pub fn menu(input: TokenStream) -> TokenStream {
+    let tree = parse_macro_input!(input as menu::MenuTree);
+
+    menu::expand(tree)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Generate a menu editing the fields of a struct, implementing `MenuForm`.
+///
+/// `bool` fields get a `CheckBox`, numbers a `Slider` when `#[menu(min = .., max = ..)]` is
+/// given and a numeric `TextInput` otherwise, `String`s a `TextInput` and every other type a
+/// `Dropdown`, which needs the type to implement `MenuChoice`. Fields can be renamed with
+/// `#[menu(label = "..")]` or left out with `#[menu(skip)]`.
+#[proc_macro_derive(MenuForm, attributes(menu))]
+pub fn menu_form(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    form::derive_menu_form(ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Implement `MenuChoice` for an enum of unit variants, so it can be picked from a `Dropdown`.
+#[proc_macro_derive(MenuChoice, attributes(menu))]
+pub fn menu_choice(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    form::derive_menu_choice(ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Implement `Interactive` for a widget, giving it the shared hover, press and click handling.
+///
+/// The fields named `position`, `size`, `color`, `visible_color`, `is_pressed`,
+/// `has_been_pressed`, `enabled` and `activation` are used by default, other fields can be picked
+/// by marking them with `#[clickable(position)]`, `#[clickable(size)]`, `#[clickable(color)]`,
+/// `#[clickable(tint)]`, `#[clickable(pressed)]`, `#[clickable(clicked)]`,
+/// `#[clickable(enabled)]` or `#[clickable(activation)]`. The last four are optional.
+#[proc_macro_derive(Clickable, attributes(clickable))]
+pub fn clickable(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    clickable::derive_clickable(ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
 }
@@ -0,0 +1,209 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{braced, bracketed, parenthesized, Error, Expr, ExprLit, ExprPath, Ident, Lit, Path, Result, Token};
+
+/// The whole input of `menu!`: one or more top level menus.
+pub struct MenuTree {
+    roots: Vec<Node>,
+}
+
+/// `let name = Type(args) [field: value, ...] { children }`
+struct Node {
+    binding: Option<Binding>,
+    path: Path,
+    args: Punctuated<Expr, Token![,]>,
+    properties: Vec<(Ident, Expr)>,
+    children: Option<(Span, Vec<Node>)>,
+}
+
+struct Binding {
+    mutability: Option<Token![mut]>,
+    name: Ident,
+}
+
+impl Parse for MenuTree {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut roots = vec![];
+        while !input.is_empty() {
+            roots.push(input.parse()?);
+        }
+        if roots.is_empty() {
+            return Err(input.error("expected at least one menu"));
+        }
+        Ok(MenuTree { roots })
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let binding = if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            let mutability = input.parse()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Some(Binding { mutability, name })
+        } else {
+            None
+        };
+
+        let path = input.parse::<ExprPath>()?.path;
+
+        let content;
+        parenthesized!(content in input);
+        let args = content.parse_terminated(Expr::parse, Token![,])?;
+
+        let mut properties = vec![];
+        if input.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            while !content.is_empty() {
+                let field: Ident = content.parse()?;
+                content.parse::<Token![:]>()?;
+                let value: Expr = content.parse()?;
+                properties.push((field, value));
+                if content.is_empty() {
+                    break;
+                }
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        let children = if input.peek(syn::token::Brace) {
+            let content;
+            let brace = braced!(content in input);
+            let mut children = vec![];
+            while !content.is_empty() {
+                children.push(content.parse()?);
+            }
+            Some((brace.span.join(), children))
+        } else {
+            None
+        };
+
+        // A `;` is needed between elements, unless they end with a children block.
+        if children.is_none() {
+            if !input.is_empty() {
+                input.parse::<Token![;]>()?;
+            }
+        } else if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+        }
+
+        Ok(Node { binding, path, args, properties, children })
+    }
+}
+
+impl Node {
+    fn is_menu(&self) -> bool {
+        self.path.segments.last().is_some_and(|segment| segment.ident == "Menu")
+    }
+
+    /// `Type::new(args)`, turning string literal arguments into `String`s.
+    fn constructor(&self) -> TokenStream {
+        let path = &self.path;
+        let args = self.args.iter().map(|arg| match arg {
+            Expr::Lit(ExprLit { lit: Lit::Str(text), .. }) => {
+                quote_spanned! {text.span()=> ::std::string::String::from(#text) }
+            }
+            _ => quote! { #arg },
+        });
+        quote_spanned! {path.span()=> #path::new(#(#args),*) }
+    }
+
+    fn assign_properties(&self, target: &Ident) -> TokenStream {
+        let assignments = self.properties.iter().map(|(field, value)| {
+            quote_spanned! {field.span()=> #target.#field = #value; }
+        });
+        quote! { #(#assignments)* }
+    }
+}
+
+struct Expander {
+    statements: Vec<TokenStream>,
+    bindings: Vec<Ident>,
+    temporaries: usize,
+}
+
+impl Expander {
+    fn check_binding(&mut self, binding: &Option<Binding>) -> Result<()> {
+        if let Some(binding) = binding {
+            if self.bindings.contains(&binding.name) {
+                return Err(Error::new(binding.name.span(), format!("`{}` is bound more than once in this menu", binding.name)));
+            }
+            self.bindings.push(binding.name.clone());
+        }
+        Ok(())
+    }
+
+    fn check_children(&self, node: &Node) -> Result<()> {
+        if let Some((span, _)) = &node.children {
+            if !node.is_menu() {
+                return Err(Error::new(*span, "only a `Menu` can have child elements"));
+            }
+        }
+        Ok(())
+    }
+
+    fn temporary(&mut self) -> Ident {
+        self.temporaries += 1;
+        format_ident!("__menu_element_{}", self.temporaries, span = Span::mixed_site())
+    }
+
+    fn root(&mut self, node: &Node) -> Result<()> {
+        self.check_children(node)?;
+        let Some(binding) = &node.binding else {
+            return Err(Error::new(node.path.span(), "a top level menu must be bound with `let`"));
+        };
+        self.check_binding(&node.binding)?;
+
+        let name = &binding.name;
+        let constructor = node.constructor();
+        self.statements.push(quote! { #[allow(unused_mut)] let mut #name = #constructor; });
+        self.statements.push(node.assign_properties(name));
+        self.children(node, name)
+    }
+
+    fn children(&mut self, node: &Node, parent: &Ident) -> Result<()> {
+        if let Some((_, children)) = &node.children {
+            for child in children {
+                self.child(child, parent)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn child(&mut self, node: &Node, parent: &Ident) -> Result<()> {
+        self.check_children(node)?;
+        self.check_binding(&node.binding)?;
+
+        let element = self.temporary();
+        let constructor = node.constructor();
+        self.statements.push(quote! { #[allow(unused_mut)] let mut #element = #constructor; });
+        self.statements.push(node.assign_properties(&element));
+        self.children(node, &element)?;
+
+        // Unbound elements have no handle to keep them alive, so the menu owns them.
+        let add = match &node.binding {
+            Some(Binding { mutability, name }) => {
+                let label = name.to_string();
+                quote! { let #mutability #name = #parent.add_named_element(#label, #element); }
+            }
+            None => quote! { #parent.add_owned_element(::std::option::Option::None, #element); },
+        };
+        self.statements.push(add);
+        Ok(())
+    }
+}
+
+pub fn expand(tree: MenuTree) -> Result<TokenStream> {
+    let mut expander = Expander { statements: vec![], bindings: vec![], temporaries: 0 };
+    for root in &tree.roots {
+        expander.root(root)?;
+    }
+
+    let statements = expander.statements;
+    Ok(quote! { #(#statements)* })
+}
@@ -0,0 +1,236 @@
+use std::cell::RefCell;
+use std::rc::Weak;
+use macroquad::prelude::*;
+
+///Easing curves used to shape the progress of a `Tween`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineInOut,
+    BackOut,
+}
+impl Easing {
+    ///Map a linear progress `t` in `0.0..=1.0` onto the curve.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::SineInOut => -((std::f32::consts::PI * t).cos() - 1.0) / 2.0,
+            Easing::BackOut => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+///A value that can be interpolated by a `Tween`.
+pub trait Lerp: Copy {
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+impl Lerp for Vec2 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+impl Lerp for Color {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Color{
+            r: self.r.lerp(to.r, t),
+            g: self.g.lerp(to.g, t),
+            b: self.b.lerp(to.b, t),
+            a: self.a.lerp(to.a, t),
+        }
+    }
+}
+
+///Interpolates a value from `from` to `to` over `duration` seconds.
+#[derive(Clone, Copy, Debug)]
+pub struct Tween<V: Lerp> {
+    pub from: V,
+    pub to: V,
+    pub duration: f32,
+    pub easing: Easing,
+
+    elapsed: f32,
+}
+impl<V: Lerp> Tween<V> {
+    pub fn new(from: V, to: V, duration: f32, easing: Easing) -> Tween<V> {
+        Tween{
+            from,
+            to,
+            duration,
+            easing,
+            elapsed: 0.0,
+        }
+    }
+
+    ///Advance the tween by `delta` seconds and return the new value.
+    pub fn step(&mut self, delta: f32) -> V {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        self.value()
+    }
+
+    ///Return the value at the current point of the tween.
+    pub fn value(&self) -> V {
+        self.from.lerp(self.to, self.easing.apply(self.progress()))
+    }
+
+    ///Return the linear progress of the tween, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        self.elapsed / self.duration
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+///Source of the time step used to advance animations.
+pub trait Clock {
+    ///Return the seconds elapsed since the last call.
+    fn delta(&mut self) -> f32;
+}
+impl<F: FnMut() -> f32> Clock for F {
+    fn delta(&mut self) -> f32 {
+        self()
+    }
+}
+
+///The default clock, driven by macroquad's `get_frame_time`.
+pub struct FrameClock;
+impl Clock for FrameClock {
+    fn delta(&mut self) -> f32 {
+        get_frame_time()
+    }
+}
+
+///A clock that advances by a fixed step on every frame.
+pub struct FixedClock(pub f32);
+impl Clock for FixedClock {
+    fn delta(&mut self) -> f32 {
+        self.0
+    }
+}
+
+trait Animation {
+    ///Advance the animation, returning `false` once it is done.
+    fn advance(&mut self, delta: f32) -> bool;
+}
+
+struct PropertyTween<T, V: Lerp> {
+    target: Weak<RefCell<T>>,
+    property: fn(&mut T) -> &mut V,
+    tween: Tween<V>,
+}
+impl<T, V: Lerp> Animation for PropertyTween<T, V> {
+    fn advance(&mut self, delta: f32) -> bool {
+        // The element was dropped, nothing left to animate.
+        let Some(target) = self.target.upgrade() else {
+            return false;
+        };
+        let value = self.tween.step(delta);
+        *(self.property)(&mut target.borrow_mut()) = value;
+
+        !self.tween.is_finished()
+    }
+}
+
+///Holds the running property tweens of a `Menu` and the clock driving them.
+pub struct Animator {
+    clock: Box<dyn Clock>,
+    animations: Vec<Box<dyn Animation>>,
+}
+impl Animator {
+    pub fn new() -> Animator {
+        Animator{
+            clock: Box::new(FrameClock),
+            animations: vec![],
+        }
+    }
+
+    ///Replace the clock used to advance the animations.
+    pub fn set_clock<C: Clock + 'static>(&mut self, clock: C) {
+        self.clock = Box::new(clock);
+    }
+
+    pub fn is_animating(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    ///Stop every running animation, leaving the properties where they are.
+    pub fn clear(&mut self) {
+        self.animations.clear();
+    }
+
+    pub(crate) fn add<T: 'static, V: Lerp + 'static>(&mut self, target: Weak<RefCell<T>>, property: fn(&mut T) -> &mut V, tween: Tween<V>) {
+        self.animations.push(Box::new(PropertyTween{
+            target,
+            property,
+            tween,
+        }));
+    }
+
+    ///Read the clock and advance every animation, returning the time step used.
+    pub(crate) fn tick(&mut self) -> f32 {
+        let delta = self.clock.delta();
+        self.advance(delta);
+
+        delta
+    }
+
+    ///Advance every animation by `delta` seconds, without reading the clock.
+    pub(crate) fn advance(&mut self, delta: f32) {
+        self.animations.retain_mut(|animation| animation.advance(delta));
+    }
+}
+impl Default for Animator {
+    fn default() -> Self {
+        Animator::new()
+    }
+}
+
+///How a `Menu` appears or disappears when shown or hidden.
+#[derive(Clone, Copy, Debug)]
+pub enum Transition {
+    Instant,
+    ///Fade the opacity in or out.
+    Fade { duration: f32, easing: Easing },
+    ///Slide from (or to) `offset` away from the menu position.
+    Slide { offset: Vec2, duration: f32, easing: Easing },
+    ///Slide and fade at the same time.
+    SlideFade { offset: Vec2, duration: f32, easing: Easing },
+}
@@ -0,0 +1,87 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+///Shared state an element can be bound to.
+///
+///A bound element picks up changes made to the state by the game, and writes the changes
+///made by the user back into it, so it never has to be copied by hand.
+pub trait Binding<T> {
+    fn get(&self) -> T;
+    fn set(&self, value: T);
+}
+impl<T: Copy> Binding<T> for Rc<Cell<T>> {
+    fn get(&self) -> T {
+        Cell::get(self)
+    }
+    fn set(&self, value: T) {
+        Cell::set(self, value)
+    }
+}
+impl<T: Clone> Binding<T> for Rc<RefCell<T>> {
+    fn get(&self) -> T {
+        self.borrow().clone()
+    }
+    fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+}
+
+///Binds to one field of a shared struct, e.g. `Lens::new(settings.clone(), |s| s.vsync, |s, v| s.vsync = v)`.
+pub struct Lens<S, T> {
+    source: Rc<RefCell<S>>,
+    get: fn(&S) -> T,
+    set: fn(&mut S, T),
+}
+impl<S, T> Lens<S, T> {
+    pub fn new(source: Rc<RefCell<S>>, get: fn(&S) -> T, set: fn(&mut S, T)) -> Lens<S, T> {
+        Lens{
+            source,
+            get,
+            set,
+        }
+    }
+}
+impl<S, T> Binding<T> for Lens<S, T> {
+    fn get(&self) -> T {
+        (self.get)(&self.source.borrow())
+    }
+    fn set(&self, value: T) {
+        (self.set)(&mut self.source.borrow_mut(), value)
+    }
+}
+
+///A binding along with the last value synced through it, to tell which side changed.
+pub(crate) struct Bound<T> {
+    binding: Box<dyn Binding<T>>,
+    last: T,
+}
+impl<T: Clone + PartialEq> Bound<T> {
+    pub(crate) fn new<B: Binding<T> + 'static>(binding: B) -> Bound<T> {
+        Bound{
+            last: binding.get(),
+            binding: Box::new(binding),
+        }
+    }
+
+    ///Return the value currently held by the binding.
+    pub(crate) fn value(&self) -> T {
+        self.last.clone()
+    }
+
+    ///Copy a change made to the binding by the game into the element's value.
+    pub(crate) fn pull(&mut self, value: &mut T) {
+        let current = self.binding.get();
+        if current != self.last {
+            *value = current.clone();
+            self.last = current;
+        }
+    }
+
+    ///Write a change made to the element's value back into the binding.
+    pub(crate) fn push(&mut self, value: &T) {
+        if *value != self.last {
+            self.last = value.clone();
+            self.binding.set(value.clone());
+        }
+    }
+}
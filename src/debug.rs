@@ -0,0 +1,62 @@
+//! A debug overlay showing how menus are laid out.
+//!
+//! While it is on, every menu outlines its background with the `edge` padding, the menu and title
+//! rectangles from its layout, and the bounding rectangle of each element, labeled with the
+//! element's id and type. The element under the pointer is highlighted.
+//!
+//! ```no_run
+//! # use Simples_menu::macroquad::prelude::*;
+//! if is_key_pressed(KeyCode::F3) {
+//!     Simples_menu::debug::set_overlay(!Simples_menu::debug::overlay());
+//! }
+//! ```
+use std::cell::Cell;
+use macroquad::prelude::*;
+use crate::render::{draw_rectangle, draw_rectangle_lines, draw_text};
+
+///The outline of the menu background, `edge` padding included.
+pub const EDGE_COLOR: Color = ORANGE;
+///The outline of the menu rectangle.
+pub const MENU_COLOR: Color = SKYBLUE;
+///The outline of the title rectangle.
+pub const TITLE_COLOR: Color = MAGENTA;
+///The outline of each element.
+pub const ELEMENT_COLOR: Color = YELLOW;
+///The fill of the element under the pointer.
+pub const HOVERED_COLOR: Color = Color{ r: 1.0, g: 1.0, b: 0.0, a: 0.25 };
+
+const LABEL_SIZE: f32 = 10.0;
+
+thread_local! {
+    static OVERLAY: Cell<bool> = const { Cell::new(false) };
+}
+
+///Show or hide the debug overlay of every menu on this thread. Hidden by default.
+pub fn set_overlay(enabled: bool) {
+    OVERLAY.with(|current| current.set(enabled));
+}
+
+pub fn overlay() -> bool {
+    OVERLAY.with(|current| current.get())
+}
+
+///Outline `rect` with `color` and write `label` in its top left corner.
+pub(crate) fn draw_labeled_outline(rect: Rect, label: &str, color: Color) {
+    draw_rectangle_lines(rect, color);
+    draw_text(label, rect.x + 2.0, rect.y + LABEL_SIZE, LABEL_SIZE, color);
+}
+
+///Outline the element `rect`, filling it when it is the one under the pointer.
+pub(crate) fn draw_element_outline(rect: Rect, id: usize, type_name: &str, hovered: bool) {
+    if hovered {
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, HOVERED_COLOR);
+    }
+    draw_labeled_outline(rect, &format!("#{id} {type_name}"), ELEMENT_COLOR);
+}
+
+///Return a type name without the module paths, e.g. `Dropdown<Quality>`.
+pub(crate) fn short_type_name(name: &str) -> String {
+    name.split_inclusive(|character: char| !(character.is_alphanumeric() || character == '_' || character == ':'))
+        .map(|part| part.rsplit("::").next().unwrap_or(part))
+        .collect()
+}
@@ -0,0 +1,100 @@
+//! Translation of titles through per-language string tables.
+//!
+//! A `Title` with a **key** shows the translation of that key in the current language,
+//! falling back to the default language and then to its **name**:
+//!
+//! ```ignore
+//! i18n::add_language("en", StringTable::parse("menu.start = Start")?);
+//! i18n::add_language("pt", StringTable::parse("menu.start = Iniciar")?);
+//! i18n::set_language("pt");
+//! button.edit().title.key = Some("menu.start".to_string());
+//! ```
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+///The translations of one language, read from `key = value` lines.
+#[derive(Clone, Default, Debug)]
+pub struct StringTable {
+    entries: HashMap<String, String>,
+}
+impl StringTable {
+    pub fn new() -> StringTable {
+        StringTable::default()
+    }
+
+    ///Read a table from `key = value` lines. Blank lines and lines starting with `#` are
+    ///skipped, and `\n` in a value is turned into a line break.
+    pub fn parse(text: &str) -> io::Result<StringTable> {
+        let mut table = StringTable::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line {}: expected `key = value`", index + 1)));
+            };
+            table.insert(key.trim(), &value.trim().replace("\\n", "\n"));
+        }
+        Ok(table)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<StringTable> {
+        StringTable::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.entries.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+}
+
+struct Locale {
+    tables: HashMap<String, StringTable>,
+    language: String,
+    default_language: String,
+}
+
+thread_local! {
+    static LOCALE: RefCell<Locale> = RefCell::new(Locale{
+        tables: HashMap::new(),
+        language: "en".to_string(),
+        default_language: "en".to_string(),
+    });
+}
+
+///Add (or replace) the string table of a language.
+pub fn add_language(language: &str, table: StringTable) {
+    LOCALE.with(|locale| locale.borrow_mut().tables.insert(language.to_string(), table));
+}
+
+///Switch the language titles are shown in. Menus pick it up on their next update.
+pub fn set_language(language: &str) {
+    LOCALE.with(|locale| locale.borrow_mut().language = language.to_string());
+}
+
+pub fn language() -> String {
+    LOCALE.with(|locale| locale.borrow().language.clone())
+}
+
+///Set the language used for keys missing from the current language, `en` by default.
+pub fn set_default_language(language: &str) {
+    LOCALE.with(|locale| locale.borrow_mut().default_language = language.to_string());
+}
+
+///Return the translation of `key` in the current language, or in the default language.
+pub fn translate(key: &str) -> Option<String> {
+    LOCALE.with(|locale| {
+        let locale = locale.borrow();
+        [&locale.language, &locale.default_language].iter()
+            .filter_map(|language| locale.tables.get(language.as_str()))
+            .find_map(|table| table.get(key))
+            .map(str::to_string)
+    })
+}
@@ -0,0 +1,527 @@
+//! Mouse and touch input, as seen by the menus.
+//!
+//! Every root `Menu` reads the pointers (the mouse, or each finger on a touch screen) from its
+//! `InputSource` once per update, and works out taps, double-clicks, right- and middle-clicks,
+//! drags and long-presses from them. The
+//! elements it updates then see those pointers through **pointers**, and the keyboard through
+//! **key_events**, **typed_chars** and **key_pressed**.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use macroquad::prelude::*;
+use crate::render::{from_screen, is_clipped};
+use crate::replay::{InputRecording, RecordedFrame};
+
+///How long a touch has to be held in place to count as a long-press, in seconds.
+pub const LONG_PRESS_TIME: f32 = 0.5;
+///How far a pointer has to move while down before it counts as a drag, on the screen.
+pub const DRAG_DISTANCE: f32 = 10.0;
+///The id of the mouse pointer, touches use the ids given by the platform.
+pub const MOUSE_ID: u64 = u64::MAX;
+///The keys menus react to, the only ones kept in an `InputRecording`.
+pub const RECORDED_KEYS: &[KeyCode] = &[
+    KeyCode::Backspace, KeyCode::Delete, KeyCode::Enter, KeyCode::Escape, KeyCode::Tab, KeyCode::Space,
+    KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down, KeyCode::Home, KeyCode::End,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointerKind {
+    Mouse,
+    Touch,
+}
+
+///How close in time and space two taps or clicks have to be to make a double-click.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DoubleClickThreshold {
+    ///The most seconds between the two.
+    pub time: f32,
+    ///The furthest apart they can be, on the screen.
+    pub distance: f32,
+}
+impl Default for DoubleClickThreshold {
+    fn default() -> Self {
+        DoubleClickThreshold{
+            time: 0.4,
+            distance: DRAG_DISTANCE,
+        }
+    }
+}
+
+thread_local! {
+    static DOUBLE_CLICK_THRESHOLD: Cell<Option<DoubleClickThreshold>> = const { Cell::new(None) };
+}
+
+///Set how close two taps or clicks have to be to make a double-click, for every menu.
+pub fn set_double_click_threshold(threshold: DoubleClickThreshold) {
+    DOUBLE_CLICK_THRESHOLD.with(|current| current.set(Some(threshold)));
+}
+
+///Return how close two taps or clicks have to be to make a double-click, see **set_double_click_threshold**.
+pub fn double_click_threshold() -> DoubleClickThreshold {
+    DOUBLE_CLICK_THRESHOLD.with(|current| current.get()).unwrap_or_default()
+}
+
+///The state of a mouse button other than the left one.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct ButtonState {
+    pub down: bool,
+    ///Went down on this frame.
+    pub pressed: bool,
+    ///Went up on this frame.
+    pub released: bool,
+}
+impl ButtonState {
+    fn of(button: MouseButton) -> ButtonState {
+        ButtonState{
+            down: is_mouse_button_down(button),
+            pressed: is_mouse_button_pressed(button),
+            released: is_mouse_button_released(button),
+        }
+    }
+}
+
+///A pointer as reported by an `InputSource`, on the screen.
+///
+///`down`, `pressed` and `released` are about the left mouse button, or the finger.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RawPointer {
+    pub id: u64,
+    pub kind: PointerKind,
+    pub position: Vec2,
+    pub down: bool,
+    ///Went down on this frame.
+    pub pressed: bool,
+    ///Went up on this frame. A cancelled touch goes away without being released.
+    pub released: bool,
+    ///The right mouse button, always up for touches.
+    pub right: ButtonState,
+    ///The middle mouse button, always up for touches.
+    pub middle: ButtonState,
+}
+impl RawPointer {
+    ///Create a pointer at `position` with every button up, for the buttons to be set afterwards.
+    pub fn new(id: u64, kind: PointerKind, position: Vec2) -> RawPointer {
+        RawPointer{
+            id,
+            kind,
+            position,
+            down: false,
+            pressed: false,
+            released: false,
+            right: ButtonState::default(),
+            middle: ButtonState::default(),
+        }
+    }
+}
+
+///Where a menu reads its pointers from, see **Menu::set_input**.
+pub trait InputSource {
+    ///Return the pointers on the screen for this frame, including the ones released on it.
+    fn poll(&mut self) -> Vec<RawPointer>;
+    ///Return the keyboard input for this frame, or `None` to read macroquad's keyboard.
+    fn poll_keys(&mut self) -> Option<KeyInput> {
+        None
+    }
+}
+
+///A character typed or a key that went down.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyEvent {
+    Char(char),
+    Key(KeyCode),
+}
+
+///The keyboard input of a frame.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct KeyInput {
+    ///The characters typed and the keys that went down, in the order they came, so typing
+    ///`a`, `b`, Backspace, `c` leaves `ac`.
+    pub events: Vec<KeyEvent>,
+}
+impl KeyInput {
+    ///Return the characters typed, in order.
+    pub fn typed(&self) -> Vec<char> {
+        self.events.iter()
+            .filter_map(|event| match event {
+                KeyEvent::Char(character) => Some(*character),
+                KeyEvent::Key(_) => None,
+            })
+            .collect()
+    }
+
+    ///Return whether `key` went down.
+    pub fn pressed(&self, key: KeyCode) -> bool {
+        self.events.contains(&KeyEvent::Key(key))
+    }
+}
+
+///Reads the mouse and macroquad's `touches()`.
+///
+///The mouse is left out while the screen is being touched, since macroquad also moves it
+///to follow the first touch.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MacroquadInput;
+impl InputSource for MacroquadInput {
+    fn poll(&mut self) -> Vec<RawPointer> {
+        let touches = touches();
+        if touches.is_empty() {
+            let (x, y) = mouse_position();
+            return vec![RawPointer{
+                id: MOUSE_ID,
+                kind: PointerKind::Mouse,
+                position: Vec2{ x, y },
+                down: is_mouse_button_down(MouseButton::Left),
+                pressed: is_mouse_button_pressed(MouseButton::Left),
+                released: is_mouse_button_released(MouseButton::Left),
+                right: ButtonState::of(MouseButton::Right),
+                middle: ButtonState::of(MouseButton::Middle),
+            }];
+        }
+
+        touches.into_iter()
+            .map(|touch| RawPointer{
+                id: touch.id,
+                kind: PointerKind::Touch,
+                position: touch.position,
+                down: matches!(touch.phase, TouchPhase::Started | TouchPhase::Moved | TouchPhase::Stationary),
+                pressed: touch.phase == TouchPhase::Started,
+                released: touch.phase == TouchPhase::Ended,
+                right: ButtonState::default(),
+                middle: ButtonState::default(),
+            })
+            .collect()
+    }
+}
+
+///A pointer on the current frame, along with the gestures worked out from the previous ones.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Pointer {
+    pub id: u64,
+    pub kind: PointerKind,
+    pub position: Vec2,
+    ///Where the pointer went down, or its position while it is up.
+    pub start: Vec2,
+    ///How far the pointer moved since the last frame.
+    pub delta: Vec2,
+    pub down: bool,
+    pub pressed: bool,
+    pub released: bool,
+    ///Released without having been dragged or long-pressed.
+    pub tapped: bool,
+    ///Tapped close enough to the last tap to make a double-click, see **double_click_threshold**.
+    pub double_clicked: bool,
+    ///The right mouse button went up near where it went down, on this frame.
+    pub right_clicked: bool,
+    ///The middle mouse button went up near where it went down, on this frame.
+    pub middle_clicked: bool,
+    ///Held in place for `LONG_PRESS_TIME`, only set on the frame it happens.
+    pub long_pressed: bool,
+    ///Moved further than `DRAG_DISTANCE` since going down.
+    pub dragging: bool,
+    ///Claimed by an element, like a slider being dragged, so containers don't scroll with it.
+    pub captured: bool,
+}
+
+struct Track {
+    start: Vec2,
+    last: Vec2,
+    held: f32,
+    dragging: bool,
+    long_pressed: bool,
+    captured: bool,
+}
+
+// What the elements of a menu see of the input while it updates.
+pub(crate) struct Frame {
+    pub(crate) pointers: Vec<Pointer>,
+    keys: Option<KeyInput>,
+    delta: f32,
+}
+
+///Turns the pointers of an `InputSource` into `Pointer`s, frame after frame.
+pub(crate) struct PointerTracker {
+    source: Box<dyn InputSource>,
+    tracks: HashMap<u64, Track>,
+    // Where the right and middle buttons of each pointer went down, while they are held.
+    right_starts: HashMap<u64, Vec2>,
+    middle_starts: HashMap<u64, Vec2>,
+    // Where the last tap was, and the seconds since, until it is too old to be doubled.
+    last_tap: Option<(Vec2, f32)>,
+    recording: Option<InputRecording>,
+}
+impl PointerTracker {
+    pub(crate) fn new() -> PointerTracker {
+        PointerTracker{
+            source: Box::new(MacroquadInput),
+            tracks: HashMap::new(),
+            right_starts: HashMap::new(),
+            middle_starts: HashMap::new(),
+            last_tap: None,
+            recording: None,
+        }
+    }
+
+    ///Start recording every frame read from the source, dropping any recording in progress.
+    pub(crate) fn start_recording(&mut self) {
+        self.recording = Some(InputRecording::new());
+    }
+
+    pub(crate) fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    pub(crate) fn set_source<S: InputSource + 'static>(&mut self, source: S) {
+        self.source = Box::new(source);
+        self.tracks.clear();
+        self.right_starts.clear();
+        self.middle_starts.clear();
+        self.last_tap = None;
+    }
+
+    ///Read the next frame from the source, `delta` seconds after the last one.
+    pub(crate) fn poll(&mut self, delta: f32) -> Frame {
+        let raw_pointers = self.source.poll();
+        let mut pointers = Vec::with_capacity(raw_pointers.len());
+        let threshold = double_click_threshold();
+        if let Some((_, age)) = &mut self.last_tap {
+            *age += delta;
+        }
+        self.last_tap = self.last_tap.filter(|(_, age)| *age <= threshold.time);
+
+        for raw in &raw_pointers {
+            if raw.pressed || (raw.down && !self.tracks.contains_key(&raw.id)) {
+                self.tracks.insert(raw.id, Track{
+                    start: raw.position,
+                    last: raw.position,
+                    held: 0.0,
+                    dragging: false,
+                    long_pressed: false,
+                    captured: false,
+                });
+            } else if let Some(track) = self.tracks.get_mut(&raw.id) {
+                track.held += delta;
+            }
+
+            let mut pointer = Pointer{
+                id: raw.id,
+                kind: raw.kind,
+                position: raw.position,
+                start: raw.position,
+                delta: Vec2::ZERO,
+                down: raw.down,
+                pressed: raw.pressed,
+                released: raw.released,
+                tapped: false,
+                double_clicked: false,
+                right_clicked: button_clicked(&mut self.right_starts, raw.id, raw.right, raw.position),
+                middle_clicked: button_clicked(&mut self.middle_starts, raw.id, raw.middle, raw.position),
+                long_pressed: false,
+                dragging: false,
+                captured: false,
+            };
+            if let Some(track) = self.tracks.get_mut(&raw.id) {
+                pointer.start = track.start;
+                pointer.delta = raw.position - track.last;
+                track.last = raw.position;
+                track.dragging |= raw.position.distance(track.start) > DRAG_DISTANCE;
+
+                if raw.down && !track.dragging && !track.long_pressed && track.held >= LONG_PRESS_TIME {
+                    track.long_pressed = true;
+                    pointer.long_pressed = true;
+                }
+                pointer.tapped = raw.released && !track.dragging && !track.long_pressed;
+                pointer.dragging = track.dragging;
+                pointer.captured = track.captured;
+            }
+            if pointer.tapped {
+                // A third tap starts a new double-click rather than making another one.
+                pointer.double_clicked = self.last_tap.is_some_and(|(position, _)| position.distance(raw.position) <= threshold.distance);
+                self.last_tap = (!pointer.double_clicked).then_some((raw.position, 0.0));
+            }
+            pointers.push(pointer);
+        }
+
+        // Forget the pointers that went up or away.
+        self.tracks.retain(|id, _| raw_pointers.iter().any(|raw| raw.id == *id && raw.down));
+        self.right_starts.retain(|id, _| raw_pointers.iter().any(|raw| raw.id == *id && raw.right.down));
+        self.middle_starts.retain(|id, _| raw_pointers.iter().any(|raw| raw.id == *id && raw.middle.down));
+
+        let mut keys = self.source.poll_keys();
+        if let Some(recording) = &mut self.recording {
+            // macroquad's keyboard is read right away, so the elements see what gets recorded.
+            let keys = keys.get_or_insert_with(|| KeyInput{ events: macroquad_key_events() });
+            recording.frames.push(RecordedFrame{
+                delta,
+                pointers: raw_pointers,
+                keys: KeyInput{
+                    events: keys.events.iter().copied().filter(|event| match event {
+                        KeyEvent::Char(_) => true,
+                        KeyEvent::Key(key) => RECORDED_KEYS.contains(key),
+                    }).collect(),
+                },
+            });
+        }
+
+        Frame{
+            pointers,
+            keys,
+            delta,
+        }
+    }
+
+    ///Keep the captures made by the elements during the update for the next frames.
+    pub(crate) fn keep_captures(&mut self, pointers: &[Pointer]) {
+        for pointer in pointers.iter().filter(|pointer| pointer.captured) {
+            if let Some(track) = self.tracks.get_mut(&pointer.id) {
+                track.captured = true;
+            }
+        }
+    }
+}
+
+///Return whether the button of the pointer `id` in `state` went up near where it went down,
+///remembering where it goes down in `starts`.
+fn button_clicked(starts: &mut HashMap<u64, Vec2>, id: u64, state: ButtonState, position: Vec2) -> bool {
+    if state.pressed {
+        starts.insert(id, position);
+    }
+    state.released && starts.get(&id).is_some_and(|start| start.distance(position) <= DRAG_DISTANCE)
+}
+
+thread_local! {
+    static FRAME: RefCell<Option<Frame>> = const { RefCell::new(None) };
+    // The ids of the pointers a menu routed to the element it is updating, if it routes them.
+    static TARGETS: RefCell<Option<Vec<u64>>> = const { RefCell::new(None) };
+}
+
+///Run `f` with the elements seeing `frame`, and return it back with the captures made in `f`.
+pub(crate) fn with_frame<R>(frame: Frame, f: impl FnOnce() -> R) -> (R, Frame) {
+    let previous = FRAME.with(|current| current.replace(Some(frame)));
+    let result = f();
+    let frame = FRAME.with(|current| current.replace(previous)).expect("the frame is set until here");
+
+    (result, frame)
+}
+
+///Return the pointers of the frame being updated, in the coordinates of the menu being updated.
+///
+///Pointers outside of the area a scrolling menu shows are left out. Outside of a menu update
+///the source is read directly, so no gesture is recognized.
+pub fn pointers() -> Vec<Pointer> {
+    let pointers = FRAME.with(|current| current.borrow().as_ref().map(|frame| frame.pointers.clone())).unwrap_or_else(|| {
+        MacroquadInput.poll().into_iter()
+            .map(|raw| Pointer{
+                id: raw.id,
+                kind: raw.kind,
+                position: raw.position,
+                start: raw.position,
+                delta: Vec2::ZERO,
+                down: raw.down,
+                pressed: raw.pressed,
+                released: raw.released,
+                tapped: raw.pressed && raw.released,
+                double_clicked: false,
+                right_clicked: raw.right.pressed && raw.right.released,
+                middle_clicked: raw.middle.pressed && raw.middle.released,
+                long_pressed: false,
+                dragging: false,
+                captured: false,
+            })
+            .collect()
+    });
+
+    pointers.into_iter()
+        .filter(|pointer| !is_clipped(pointer.position))
+        .map(|pointer| Pointer{
+            position: from_screen(pointer.position),
+            start: from_screen(pointer.start),
+            delta: from_screen(pointer.position) - from_screen(pointer.position - pointer.delta),
+            ..pointer
+        })
+        .collect()
+}
+
+///Run `f` with only the pointers in `ids` reaching **targeted_pointers**.
+pub(crate) fn with_targets<R>(ids: Vec<u64>, f: impl FnOnce() -> R) -> R {
+    let previous = TARGETS.with(|current| current.replace(Some(ids)));
+    let result = f();
+    TARGETS.with(|current| current.replace(previous));
+
+    result
+}
+
+///Return the pointers over the element being updated, as found by its menu.
+///
+///A menu looks up the element on top under each pointer once, and only hands the pointer
+///to that element, so elements don't have to check every pointer themselves.
+pub fn targeted_pointers() -> Vec<Pointer> {
+    let targets = TARGETS.with(|current| current.borrow().clone());
+    match targets {
+        Some(ids) if ids.is_empty() => vec![],
+        Some(ids) => pointers().into_iter().filter(|pointer| ids.contains(&pointer.id)).collect(),
+        None => pointers(),
+    }
+}
+
+///Claim the pointer with `id` for the element being updated until it goes up.
+pub fn capture(id: u64) {
+    FRAME.with(|current| {
+        if let Some(pointer) = current.borrow_mut().iter_mut().flat_map(|frame| &mut frame.pointers).find(|pointer| pointer.id == id) {
+            pointer.captured = true;
+        }
+    });
+}
+
+// macroquad doesn't tell the order of characters and keys, so the characters come first.
+fn macroquad_key_events() -> Vec<KeyEvent> {
+    std::iter::from_fn(get_char_pressed).map(KeyEvent::Char)
+        .chain(RECORDED_KEYS.iter().copied().filter(|key| is_key_pressed(*key)).map(KeyEvent::Key))
+        .collect()
+}
+
+///Return the characters typed and the keys menus react to that went down on the frame being
+///updated, in order.
+///
+///Read from macroquad when the source doesn't give the keyboard, which only returns the characters once.
+pub fn key_events() -> Vec<KeyEvent> {
+    let events = FRAME.with(|current| current.borrow().as_ref().and_then(|frame| frame.keys.as_ref().map(|keys| keys.events.clone())));
+    events.unwrap_or_else(macroquad_key_events)
+}
+
+///Return the characters typed on the frame being updated.
+///
+///Read from macroquad when the source doesn't give the keyboard, which only returns them once.
+pub fn typed_chars() -> Vec<char> {
+    let typed = FRAME.with(|current| current.borrow().as_ref().and_then(|frame| frame.keys.as_ref().map(KeyInput::typed)));
+    typed.unwrap_or_else(|| std::iter::from_fn(get_char_pressed).collect())
+}
+
+///Return whether `key` went down on the frame being updated.
+pub fn key_pressed(key: KeyCode) -> bool {
+    let pressed = FRAME.with(|current| current.borrow().as_ref().and_then(|frame| frame.keys.as_ref().map(|keys| keys.pressed(key))));
+    pressed.unwrap_or_else(|| is_key_pressed(key))
+}
+
+///Return the seconds since the last frame of the menu being updated, as given by its animator's clock.
+pub fn frame_time() -> f32 {
+    FRAME.with(|current| current.borrow().as_ref().map(|frame| frame.delta)).unwrap_or_else(get_frame_time)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EventKind {
+    LongPress,
+    RightClick,
+    MiddleClick,
+    DoubleClick,
+}
+
+///Something that happened to an element of a menu, see **Menu::take_events**.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MenuEvent {
+    pub kind: EventKind,
+    ///The id of the element, see **Element::id**.
+    pub element: usize,
+    ///The name the element was added with, if any.
+    pub name: Option<String>,
+    ///Where it happened, in the coordinates of the menu.
+    pub position: Vec2,
+}
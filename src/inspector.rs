@@ -0,0 +1,502 @@
+//! A panel for inspecting and editing menus while the game runs, itself built as a menu.
+//!
+//! The panel lists every menu given to **Inspector::update** along with their elements, nested
+//! menus included. Clicking one shows its position, size, color, title, visible and enabled flags,
+//! which are applied as they are edited, and the `Rust` and `RON` buttons give the new values as a snippet
+//! to paste back into the code or a layout file, see **Inspector::snippet**:
+//!
+//! ```no_run
+//! use Simples_menu::Menu;
+//! use Simples_menu::inspector::Inspector;
+//! use Simples_menu::macroquad::prelude::*;
+//!
+//! # async fn run() {
+//! let mut settings = Menu::new("Settings".to_string(), vec2(50.0, 50.0));
+//! let mut inspector = Inspector::new(vec2(500.0, 20.0));
+//! loop {
+//!     settings.update();
+//!     inspector.update(&mut [&mut settings]);
+//!     if let Some(snippet) = inspector.snippet() {
+//!         println!("{snippet}");
+//!     }
+//!     settings.draw();
+//!     inspector.draw();
+//!     next_frame().await
+//! }
+//! # }
+//! ```
+use macroquad::prelude::*;
+use crate::{Button, CheckBox, Element, Menu, MenuElement, PositionType, Slider, TextInput, TextLabel};
+
+const ROW_HEIGHT: f32 = 22.0;
+const FIELD_WIDTH: f32 = 60.0;
+const FIELD_HEIGHT: f32 = 18.0;
+const SELECTED_COLOR: Color = Color{ r: 0.2, g: 0.45, b: 0.75, a: 1.0 };
+// The element types `loader` can build, the only ones written as RON.
+const LOADER_TYPES: &[&str] = &["Menu", "Button", "CheckBox", "TextLabel"];
+
+///The properties of an element the inspector edits, `None` for the ones it doesn't have.
+///
+///See **MenuElement::properties**.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Properties {
+    pub position: Option<Vec2>,
+    pub size: Option<Vec2>,
+    pub color: Option<Color>,
+    ///The title of a menu, or the label of a button or text label.
+    pub title: Option<String>,
+    pub visible: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+///Set `field` to `value`, if there is one.
+pub(crate) fn set_if<T: Clone>(field: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *field = value.clone();
+    }
+}
+
+///What the inspector has selected: the index of a root menu followed by the ids of the elements down to it.
+struct Selection {
+    path: Vec<usize>,
+    type_name: String,
+    name: Option<String>,
+}
+
+enum Target<'a> {
+    Root(&'a mut Menu),
+    Element(Element<dyn MenuElement>),
+}
+impl Target<'_> {
+    fn properties(&self) -> Properties {
+        match self {
+            Target::Root(menu) => menu.properties(),
+            Target::Element(element) => element.read().properties(),
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        match self {
+            Target::Root(menu) => menu.set_properties(properties),
+            Target::Element(element) => element.edit().set_properties(properties),
+        }
+    }
+}
+
+///Find the menu or element at `path`, see `Selection`.
+fn resolve<'a>(menus: &'a mut [&mut Menu], path: &[usize]) -> Option<Target<'a>> {
+    let (root, ids) = path.split_first()?;
+    let menu: &'a mut Menu = menus.get_mut(*root)?;
+    let Some((first, ids)) = ids.split_first() else {
+        return Some(Target::Root(menu));
+    };
+
+    let mut element = menu.elements.iter().find(|element| element.id() == *first)?.clone();
+    for id in ids {
+        let child = element.read().children().iter().find(|child| child.id() == *id).cloned();
+        element = child?;
+    }
+    Some(Target::Element(element))
+}
+
+///A line of the tree shown by the inspector.
+#[derive(PartialEq)]
+struct Node {
+    path: Vec<usize>,
+    label: String,
+}
+
+fn tree(menus: &[&mut Menu]) -> Vec<Node> {
+    fn walk(elements: &[Element<dyn MenuElement>], path: &mut Vec<usize>, nodes: &mut Vec<Node>) {
+        for element in elements {
+            path.push(element.id());
+            let indent = "  ".repeat(path.len() - 1);
+            let type_name = crate::debug::short_type_name(element.type_name());
+            let name = element.name().map(|name| format!(" {name}")).unwrap_or_default();
+            nodes.push(Node{ path: path.clone(), label: format!("{indent}#{} {type_name}{name}", element.id()) });
+            walk(element.read().children(), path, nodes);
+            path.pop();
+        }
+    }
+
+    let mut nodes = vec![];
+    for (index, menu) in menus.iter().enumerate() {
+        let mut path = vec![index];
+        nodes.push(Node{ path: path.clone(), label: format!("Menu {}", menu.title.name) });
+        walk(&menu.elements, &mut path, &mut nodes);
+    }
+    nodes
+}
+
+///The elements editing the properties of the selection.
+struct Fields {
+    labels: [Element<TextLabel>; 6],
+    x: Element<TextInput>,
+    y: Element<TextInput>,
+    width: Element<TextInput>,
+    height: Element<TextInput>,
+    color: [Element<Slider>; 4],
+    title: Element<TextInput>,
+    visible: Element<CheckBox>,
+    enabled: Element<CheckBox>,
+    rust: Element<Button>,
+    ron: Element<Button>,
+}
+impl Fields {
+    fn new(menu: &mut Menu) -> Fields {
+        let mut label = |text: &str| menu.add_element(TextLabel::new(text.to_string(), PositionType::TopLeft, Vec2::ZERO));
+        let labels = [label("position"), label("size"), label("color"), label("title"), label("visible"), label("enabled")];
+        let mut number = || {
+            let mut input = TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(FIELD_WIDTH, FIELD_HEIGHT));
+            input.numeric = true;
+            menu.add_element(input)
+        };
+        let (x, y, width, height) = (number(), number(), number(), number());
+        let mut channel = || menu.add_element(Slider::new(PositionType::TopLeft, Vec2::ZERO, vec2(FIELD_WIDTH, 10.0), 0.0, 1.0, 0.0));
+        let color = [channel(), channel(), channel(), channel()];
+
+        let fields = Fields{
+            labels,
+            x,
+            y,
+            width,
+            height,
+            color,
+            title: menu.add_element(TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(FIELD_WIDTH * 2.0 + 5.0, FIELD_HEIGHT))),
+            visible: menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(FIELD_HEIGHT, FIELD_HEIGHT))),
+            enabled: menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(FIELD_HEIGHT, FIELD_HEIGHT))),
+            rust: menu.add_element(Button::new("Rust".to_string(), PositionType::TopLeft, Vec2::ZERO, Some(vec2(FIELD_WIDTH, FIELD_HEIGHT)))),
+            ron: menu.add_element(Button::new("RON".to_string(), PositionType::TopLeft, Vec2::ZERO, Some(vec2(FIELD_WIDTH, FIELD_HEIGHT)))),
+        };
+        fields.show(None);
+        fields
+    }
+
+    ///Lay the fields out in rows below `top`.
+    fn place(&self, top: f32) {
+        let row = |index: f32| top + index * ROW_HEIGHT;
+        let columns = [FIELD_WIDTH, FIELD_WIDTH * 2.0 + 5.0];
+        // Text labels are drawn from their baseline.
+        for (label, index) in self.labels.iter().zip([0.0, 1.0, 2.0, 4.0, 5.0, 6.0]) {
+            label.edit().position = vec2(0.0, row(index) + 13.0);
+        }
+        self.x.edit().position = vec2(columns[0], row(0.0));
+        self.y.edit().position = vec2(columns[1], row(0.0));
+        self.width.edit().position = vec2(columns[0], row(1.0));
+        self.height.edit().position = vec2(columns[1], row(1.0));
+        for (index, channel) in self.color.iter().enumerate() {
+            channel.edit().position = vec2(columns[index % 2], row(2.0 + (index / 2) as f32) + 4.0);
+        }
+        self.title.edit().position = vec2(columns[0], row(4.0));
+        self.visible.edit().position = vec2(columns[0], row(5.0));
+        self.enabled.edit().position = vec2(columns[0], row(6.0));
+        self.rust.edit().position = vec2(0.0, row(7.0));
+        self.ron.edit().position = vec2(columns[0] + 5.0, row(7.0));
+    }
+
+    ///Show `properties` in the fields, hiding the ones of properties left out, or every field for `None`.
+    ///Text fields being typed in are left alone.
+    fn show(&self, properties: Option<&Properties>) {
+        let empty = Properties::default();
+        let properties = properties.unwrap_or(&empty);
+        let has = [
+            properties.position.is_some(),
+            properties.size.is_some(),
+            properties.color.is_some(),
+            properties.title.is_some(),
+            properties.visible.is_some(),
+            properties.enabled.is_some(),
+        ];
+        for (label, has) in self.labels.iter().zip(has) {
+            label.edit().visible = has;
+        }
+
+        let show_number = |input: &Element<TextInput>, value: Option<f32>| {
+            let mut input = input.edit();
+            input.visible = value.is_some();
+            if let Some(value) = value.filter(|_| !input.is_focused) {
+                input.text = value.to_string();
+            }
+        };
+        show_number(&self.x, properties.position.map(|position| position.x));
+        show_number(&self.y, properties.position.map(|position| position.y));
+        show_number(&self.width, properties.size.map(|size| size.x));
+        show_number(&self.height, properties.size.map(|size| size.y));
+
+        let channels = properties.color.map(|color| [color.r, color.g, color.b, color.a]);
+        for (index, channel) in self.color.iter().enumerate() {
+            let mut channel = channel.edit();
+            channel.visible = channels.is_some();
+            if let Some(channels) = channels {
+                channel.value = channels[index];
+            }
+        }
+
+        let mut title = self.title.edit();
+        title.visible = properties.title.is_some();
+        if let Some(text) = properties.title.as_ref().filter(|_| !title.is_focused) {
+            title.text = text.clone();
+        }
+        for (check_box, value) in [(&self.visible, properties.visible), (&self.enabled, properties.enabled)] {
+            let mut check_box = check_box.edit();
+            check_box.visible = value.is_some();
+            check_box.is_checked = value.unwrap_or(false);
+        }
+
+        let is_selected = properties != &empty;
+        self.rust.edit().visible = is_selected;
+        self.ron.edit().visible = is_selected;
+    }
+
+    ///Return `shown` with the values typed or picked in the fields, keeping those that don't parse.
+    fn read(&self, shown: &Properties) -> Properties {
+        let number = |input: &Element<TextInput>, fallback: f32| input.read().text.trim().parse().unwrap_or(fallback);
+
+        let mut properties = shown.clone();
+        if let Some(position) = &mut properties.position {
+            *position = vec2(number(&self.x, position.x), number(&self.y, position.y));
+        }
+        if let Some(size) = &mut properties.size {
+            *size = vec2(number(&self.width, size.x), number(&self.height, size.y));
+        }
+        if let Some(color) = &mut properties.color {
+            let [r, g, b, a] = [0, 1, 2, 3].map(|index| self.color[index].read().value);
+            *color = Color{ r, g, b, a };
+        }
+        if let Some(title) = &mut properties.title {
+            *title = self.title.read().text.clone();
+        }
+        if let Some(visible) = &mut properties.visible {
+            *visible = self.visible.read().is_checked;
+        }
+        if let Some(enabled) = &mut properties.enabled {
+            *enabled = self.enabled.read().is_checked;
+        }
+        properties
+    }
+}
+
+///A panel listing menus and their elements, editing the properties of the one selected.
+pub struct Inspector {
+    ///The panel, which can be moved or styled like any other menu.
+    pub menu: Menu,
+
+    rows: Vec<(Node, Element<Button>)>,
+    fields: Fields,
+    selection: Option<Selection>,
+    // The properties of the selection as last shown in, or applied from, the fields.
+    shown: Option<Properties>,
+    // The snippet asked for on the last update.
+    snippet: Option<String>,
+}
+impl Inspector {
+    pub fn new(position: Vec2) -> Inspector {
+        let mut menu = Menu::new("Inspector".to_string(), position);
+        menu.title.font_size = 18.0;
+        let fields = Fields::new(&mut menu);
+        fields.place(0.0);
+
+        Inspector{
+            menu,
+            rows: vec![],
+            fields,
+            selection: None,
+            shown: None,
+            snippet: None,
+        }
+    }
+
+    ///Update the panel, listing `menus` and applying the edits made to the selected one or to
+    ///one of their elements. Call it after updating `menus`, with them in the same order every frame.
+    ///Does nothing while the panel is hidden.
+    pub fn update(&mut self, menus: &mut [&mut Menu]) {
+        self.snippet = None;
+        if !self.menu.visible {
+            return;
+        }
+        let nodes = tree(menus);
+        if self.rows.iter().map(|(node, _)| node).ne(nodes.iter()) {
+            self.show_tree(nodes);
+        }
+        self.menu.update();
+
+        let clicked = self.rows.iter()
+            .find(|(_, button)| button.read().has_been_pressed)
+            .map(|(node, _)| node.path.clone());
+        if let Some(path) = clicked {
+            self.select(menus, path);
+        }
+
+        let Some(mut target) = self.selection.as_ref().and_then(|selection| resolve(menus, &selection.path)) else {
+            if self.selection.take().is_some() {
+                self.shown = None;
+                self.fields.show(None);
+                self.highlight_selected_row();
+            }
+            return;
+        };
+        let current = target.properties();
+        match &self.shown {
+            Some(shown) => {
+                let edited = self.fields.read(shown);
+                if edited != *shown {
+                    target.set_properties(&edited);
+                    self.shown = Some(edited);
+                } else if current != *shown {
+                    // Changed by the game or an animation.
+                    self.fields.show(Some(&current));
+                    self.shown = Some(current);
+                }
+            }
+            None => {
+                self.fields.show(Some(&current));
+                self.shown = Some(current);
+            }
+        }
+
+        // Only the types the loader knows can be written as RON.
+        if self.fields.ron.read().visible && self.ron_snippet().is_none() {
+            self.fields.ron.edit().visible = false;
+        }
+
+        if self.fields.rust.read().has_been_pressed {
+            self.snippet = self.rust_snippet();
+        }
+        if self.fields.ron.read().has_been_pressed {
+            self.snippet = self.ron_snippet();
+        }
+    }
+
+    ///Return the snippet asked for with the `Rust` or `RON` button on the last update, for the
+    ///game to print or copy.
+    pub fn snippet(&self) -> Option<&str> {
+        self.snippet.as_deref()
+    }
+
+    pub fn draw(&self) {
+        self.menu.draw();
+    }
+
+    ///Select the menu or element at `path` in `menus`, the index of a root menu followed by the
+    ///ids of the elements down to it. Does nothing when there is none there.
+    pub fn select(&mut self, menus: &mut [&mut Menu], path: Vec<usize>) {
+        let Some(target) = resolve(menus, &path) else {
+            return;
+        };
+        let (type_name, name) = match &target {
+            Target::Root(_) => ("Menu".to_string(), None),
+            Target::Element(element) => (crate::debug::short_type_name(element.type_name()), element.name().map(str::to_string)),
+        };
+        self.selection = Some(Selection{ path, type_name, name });
+        self.shown = None;
+        self.highlight_selected_row();
+    }
+
+    ///Return the path of the selected menu or element, see **select**.
+    pub fn selected(&self) -> Option<&[usize]> {
+        self.selection.as_ref().map(|selection| selection.path.as_slice())
+    }
+
+    ///Return the properties of the selection, as last shown or edited.
+    pub fn properties(&self) -> Option<&Properties> {
+        self.shown.as_ref()
+    }
+
+    ///Return Rust statements setting the properties of the selection, through the element handle
+    ///named after it, or `menu` for a root menu.
+    pub fn rust_snippet(&self) -> Option<String> {
+        let selection = self.selection.as_ref()?;
+        let properties = self.shown.as_ref()?;
+        let is_root = selection.path.len() == 1;
+        let target = match &selection.name {
+            _ if is_root => "menu".to_string(),
+            Some(name) => format!("{name}.edit()"),
+            None => format!("{}.edit()", selection.type_name.to_lowercase()),
+        };
+
+        let mut lines = vec![];
+        if let Some(position) = properties.position {
+            lines.push(format!("{target}.position = vec2({}, {});", number(position.x), number(position.y)));
+        }
+        if let Some(size) = properties.size {
+            let size = format!("vec2({}, {})", number(size.x), number(size.y));
+            // The size of a menu is optional, following its elements when unset.
+            let size = if selection.type_name == "Menu" { format!("Some({size})") } else { size };
+            lines.push(format!("{target}.size = {size};"));
+        }
+        if let Some(color) = properties.color {
+            lines.push(format!("{target}.color = Color::new({}, {}, {}, {});", number(color.r), number(color.g), number(color.b), number(color.a)));
+        }
+        if let Some(title) = &properties.title {
+            lines.push(format!("{target}.title.name = {:?}.to_string();", title));
+        }
+        if let Some(visible) = properties.visible {
+            lines.push(format!("{target}.visible = {visible};"));
+        }
+        if let Some(enabled) = properties.enabled {
+            lines.push(format!("{target}.enabled = {enabled};"));
+        }
+        Some(lines.join("\n"))
+    }
+
+    ///Return the selection written the way `loader` reads it, without the elements of a menu.
+    ///`None` for the types the loader can't build, like sliders and custom widgets.
+    pub fn ron_snippet(&self) -> Option<String> {
+        let selection = self.selection.as_ref()?;
+        let properties = self.shown.as_ref()?;
+        if !LOADER_TYPES.contains(&selection.type_name.as_str()) {
+            return None;
+        }
+
+        let mut fields = vec![];
+        if let Some(name) = &selection.name {
+            fields.push(format!("name: {:?}", name));
+        }
+        if let Some(title) = &properties.title {
+            let field = if selection.type_name == "Menu" { "title" } else { "label" };
+            fields.push(format!("{field}: {:?}", title));
+        }
+        if let Some(position) = properties.position {
+            fields.push(format!("position: ({}, {})", number(position.x), number(position.y)));
+        }
+        if let Some(size) = properties.size {
+            fields.push(format!("size: ({}, {})", number(size.x), number(size.y)));
+        }
+        if let Some(color) = properties.color {
+            fields.push(format!("color: ({}, {}, {}, {})", number(color.r), number(color.g), number(color.b), number(color.a)));
+        }
+        if let Some(visible) = properties.visible {
+            fields.push(format!("visible: {visible}"));
+        }
+        if let Some(enabled) = properties.enabled {
+            fields.push(format!("enabled: {enabled}"));
+        }
+        Some(format!("{}({})", selection.type_name, fields.join(", ")))
+    }
+
+    ///List `nodes` as buttons above the fields.
+    fn show_tree(&mut self, nodes: Vec<Node>) {
+        // The buttons of the old rows leave the menu along with their handles.
+        self.rows = nodes.into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let button = Button::new(node.label.clone(), PositionType::TopLeft, vec2(0.0, index as f32 * ROW_HEIGHT), None);
+                (node, self.menu.add_element(button))
+            })
+            .collect();
+        self.fields.place(self.rows.len() as f32 * ROW_HEIGHT + 10.0);
+        self.highlight_selected_row();
+    }
+
+    fn highlight_selected_row(&self) {
+        for (node, button) in &self.rows {
+            let is_selected = self.selected() == Some(node.path.as_slice());
+            button.edit().color = if is_selected { SELECTED_COLOR } else { GRAY };
+        }
+    }
+}
+
+///Write `value` rounded to 3 decimals, always with a decimal point so it reads as a float.
+fn number(value: f32) -> String {
+    format!("{:?}", (value * 1000.0).round() / 1000.0)
+}
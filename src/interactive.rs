@@ -0,0 +1,192 @@
+use std::cell::Cell;
+use macroquad::prelude::*;
+use crate::Title;
+use crate::input::{pointers, targeted_pointers, PointerKind};
+use crate::render::{draw_rectangle, draw_text, measure_text};
+
+///What happened to an `Interactive` element during the last update.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct Interaction {
+    pub hovered: bool,
+    ///The mouse button or a finger is held down over the element.
+    pub pressed: bool,
+    ///The element was activated on this frame, see `Activation`.
+    pub clicked: bool,
+    ///The mouse button or a finger was let go over the element on this frame.
+    pub released: bool,
+    ///A finger has been held on the element for `input::LONG_PRESS_TIME`, set on one frame only.
+    pub long_pressed: bool,
+    ///The element was right-clicked on this frame.
+    pub right_clicked: bool,
+    ///The element was middle-clicked on this frame.
+    pub middle_clicked: bool,
+    ///The element was tapped or clicked a second time on this frame, see `input::double_click_threshold`.
+    pub double_clicked: bool,
+    ///The id of the pointer over the element, see `input::pointers`.
+    pub pointer: Option<u64>,
+}
+
+///When a clickable element is activated.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Activation {
+    ///When the mouse button or finger that went down on the element is let go over it, so
+    ///dragging off the element cancels the click. Touches that were dragged don't count.
+    #[default]
+    OnRelease,
+    ///As soon as the mouse button or a finger goes down on the element.
+    OnPress,
+}
+
+///Hover, press and click handling shared by the clickable elements.
+///
+///Usually implemented with `#[derive(Clickable)]`, which maps the methods onto the
+///`position`, `size`, `color`, `visible_color`, `is_pressed`, `has_been_pressed`, `enabled`,
+///`activation` and `disabled_reason` fields, or onto the fields marked with `#[clickable(..)]`.
+///
+///A disabled element ignores input and is drawn with the `DisabledColors`, and its
+///`disabled_reason` is shown in its tooltip while it is hovered, e.g. to say why it is disabled.
+pub trait Interactive {
+    ///Return the area of the element reacting to the mouse, on the screen.
+    fn hit_rect(&self, menu_position: Vec2) -> Rect;
+    ///Return the color of the element when it isn't hovered.
+    fn base_color(&self) -> Color;
+    ///Set the color the element is drawn with.
+    fn set_tint(&mut self, color: Color);
+    fn set_pressed(&mut self, _is_pressed: bool) {}
+    fn set_clicked(&mut self, _has_been_pressed: bool) {}
+    ///A disabled element is drawn with the `DisabledColors` and only reports being hovered.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    fn activation(&self) -> Activation {
+        Activation::OnRelease
+    }
+    ///Return what the tooltip of the element says while it is disabled.
+    fn disabled_reason(&self) -> Option<String> {
+        None
+    }
+
+    ///Check the pointers against the element, updating its tint and press flags.
+    ///
+    ///Each finger is checked on its own, so lifting one doesn't release an element held by another.
+    ///The menu keeps handing a pointer to the element it went down on until it goes up, so
+    ///releasing it anywhere else cancels the click.
+    fn update_interaction(&mut self, menu_position: Vec2) -> Interaction {
+        let mut interaction = Interaction::default();
+        self.set_tint(self.base_color());
+
+        let hit_rect = self.hit_rect(menu_position);
+        let activation = self.activation();
+        for pointer in targeted_pointers().iter().filter(|pointer| hit_rect.contains(pointer.position)) {
+            interaction.hovered = true;
+            // A finger dragging across the element is scrolling, not pressing it.
+            interaction.pressed |= pointer.down && !(pointer.kind == PointerKind::Touch && pointer.dragging);
+            interaction.clicked |= match activation {
+                Activation::OnPress => pointer.pressed,
+                Activation::OnRelease => hit_rect.contains(pointer.start) && match pointer.kind {
+                    PointerKind::Mouse => pointer.released,
+                    PointerKind::Touch => pointer.tapped,
+                },
+            };
+            interaction.released |= pointer.released;
+            interaction.long_pressed |= pointer.long_pressed;
+            interaction.right_clicked |= pointer.right_clicked;
+            interaction.middle_clicked |= pointer.middle_clicked;
+            interaction.double_clicked |= pointer.double_clicked && hit_rect.contains(pointer.start);
+            interaction.pointer = interaction.pointer.or(Some(pointer.id));
+        }
+        if !self.is_enabled() {
+            interaction = Interaction{ hovered: interaction.hovered, ..Interaction::default() };
+            self.set_tint(disabled_colors().background);
+        } else if interaction.pressed {
+            self.set_tint(pressed_color(self.base_color()));
+        } else if interaction.hovered {
+            self.set_tint(hover_color(self.base_color()));
+        }
+        self.set_pressed(interaction.pressed);
+        self.set_clicked(interaction.clicked);
+
+        interaction
+    }
+}
+
+///Return the position of the pointer held down, or else of the mouse, in the coordinates of the menu being updated.
+pub(crate) fn pointer_position() -> Vec2 {
+    let pointers = pointers();
+    pointers.iter()
+        .find(|pointer| pointer.down)
+        .or(pointers.first())
+        .map_or(Vec2{ x: f32::NEG_INFINITY, y: f32::NEG_INFINITY }, |pointer| pointer.position)
+}
+
+///Return whether a pointer went down anywhere on this frame.
+pub(crate) fn pointer_pressed() -> bool {
+    pointers().iter().any(|pointer| pointer.pressed)
+}
+
+///The colors disabled elements are drawn with, see `set_disabled_colors`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DisabledColors {
+    ///The color of the element itself, in place of its own `color`.
+    pub background: Color,
+    ///The color of the text, check mark or handle drawn on it.
+    pub text: Color,
+}
+impl Default for DisabledColors {
+    fn default() -> Self {
+        DisabledColors{
+            background: Color{ r: 0.35, g: 0.35, b: 0.35, a: 1.0 },
+            text: Color{ r: 0.6, g: 0.6, b: 0.6, a: 1.0 },
+        }
+    }
+}
+
+thread_local! {
+    static DISABLED_COLORS: Cell<Option<DisabledColors>> = const { Cell::new(None) };
+}
+
+///Set the colors every disabled element on this thread is drawn with.
+pub fn set_disabled_colors(colors: DisabledColors) {
+    DISABLED_COLORS.with(|current| current.set(Some(colors)));
+}
+
+pub fn disabled_colors() -> DisabledColors {
+    DISABLED_COLORS.with(|current| current.get()).unwrap_or_default()
+}
+
+///Return the darker color used while the element is hovered.
+pub(crate) fn hover_color(color: Color) -> Color {
+    Color{
+        r: color.r - 0.1,
+        g: color.g - 0.1,
+        b: color.b - 0.1,
+        a: color.a,
+    }
+}
+
+///Return the even darker color used while the element is held down.
+pub(crate) fn pressed_color(color: Color) -> Color {
+    Color{
+        r: color.r - 0.2,
+        g: color.g - 0.2,
+        b: color.b - 0.2,
+        a: color.a,
+    }
+}
+
+///Draw a rectangle with the title centered on it in `text_color`, like a button.
+pub(crate) fn draw_titled_rect(position: Vec2, size: Vec2, color: Color, title: &Title, text_color: Color) {
+    draw_rectangle(position.x,
+                   position.y,
+                   size.x,
+                   size.y,
+                   color);
+
+    let text = title.text();
+    let text_size = measure_text(&text, title.font_size as u16);
+    draw_text(&text,
+              position.x + (size.x - text_size.width) / 2.0,
+              position.y + (size.y + text_size.height) / 2.0,
+              title.font_size,
+              text_color)
+}
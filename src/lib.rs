@@ -1,12 +1,53 @@
 use std::borrow::{Borrow, BorrowMut};
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 use macroquad::prelude::*;
 use macros::*;
 use crate::PositionType::*;
-
-#[derive(PartialEq)]
+use crate::animation::*;
+use crate::binding::{Binding, Bound};
+use crate::state::{child_key, property_key};
+use crate::inspector::set_if;
+use crate::input::{pointers, targeted_pointers, with_targets, EventKind, InputSource, KeyEvent, PointerKind, PointerTracker};
+use crate::replay::{InputRecording, Replay};
+use crate::spatial::SpatialIndex;
+use crate::interactive::{draw_titled_rect, pointer_position, pointer_pressed};
+use crate::tooltip::SharedTooltip;
+use crate::render::{draw_circle, draw_deferred, draw_later, draw_rectangle, draw_text, measure_text, rect_to_screen, screen_scale, with_clip, with_opacity, with_scale};
+
+pub use macroquad;
+pub use macros::{menu, Clickable, MenuChoice, MenuForm};
+pub use crate::input::MenuEvent;
+pub use crate::interactive::{disabled_colors, set_disabled_colors, Activation, DisabledColors, Interaction, Interactive};
+pub use crate::inspector::Properties;
+pub use crate::state::MenuState;
+pub use crate::render::{set_ui_scale, ui_scale};
+pub use crate::tooltip::Tooltip;
+
+extern crate self as Simples_menu;
+
+pub mod animation;
+pub mod binding;
+pub mod debug;
+pub mod i18n;
+pub mod input;
+pub mod inspector;
+pub mod state;
+mod interactive;
+#[cfg(any(feature = "ron", feature = "json"))]
+pub mod loader;
+pub mod render;
+pub mod replay;
+#[cfg(feature = "software")]
+pub mod software;
+pub mod spatial;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+pub mod testing;
+pub mod tooltip;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum PositionType {
     TopLeft,
     Center
@@ -16,18 +57,77 @@ pub trait MenuElement {
     fn update(&mut self, menu_position: Vec2);
     fn draw(&self, menu_position: Vec2);
     fn bounding_rect(&self) -> Option<Rect>;
+    ///Save the user-facing state of the element under `key`, see **Menu::save_state**.
+    fn save_state(&self, _key: &str, _state: &mut MenuState) {}
+    ///Restore the state saved by **save_state**, leaving the element as it is if `key` is missing.
+    fn load_state(&mut self, _key: &str, _state: &MenuState) {}
+    ///Return the properties shown in the `inspector`, leaving out the ones the element doesn't have.
+    fn properties(&self) -> Properties {
+        Properties::default()
+    }
+    ///Apply the properties edited in the `inspector`, ignoring the ones the element doesn't have.
+    fn set_properties(&mut self, _properties: &Properties) {}
+    ///Return the text shown in the element's tooltip for now, in place of the one set with
+    ///**Element::set_tooltip**, like why a disabled element is disabled.
+    fn tooltip(&self) -> Option<String> {
+        self.interactive().filter(|element| !element.is_enabled()).and_then(|element| element.disabled_reason())
+    }
+    ///Return whether the element takes input, see **Interactive::is_enabled**.
+    fn is_enabled(&self) -> bool {
+        self.interactive().is_none_or(|element| element.is_enabled())
+    }
+    ///Return the element as `Interactive`, for the menu to see whether it is enabled and why not.
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        None
+    }
+    ///Return the area the element draws above the others, like the list of an open dropdown.
+    ///
+    ///Pointers over it go to the element first, whatever is under it.
+    fn overlay_rect(&self) -> Option<Rect> {
+        None
+    }
+    ///Return the elements inside this one, like those of a nested menu.
+    fn children(&self) -> &[Element<dyn MenuElement>] {
+        &[]
+    }
+}
+
+///A struct that can be edited through a generated menu, see `#[derive(MenuForm)]`.
+pub trait MenuForm {
+    ///The generated menu along with the handles of its elements.
+    type Form;
+    ///Build a menu showing the current values of `self`.
+    fn to_menu(&self, title: &str, position: Vec2) -> Self::Form;
+    ///Write the values edited in the menu back into `self`.
+    fn read_menu(&mut self, form: &Self::Form);
+}
+
+///A type that can be picked from a `Dropdown` in a `MenuForm`, see `#[derive(MenuChoice)]`.
+pub trait MenuChoice: Sized {
+    ///Every value that can be picked, in the order they are listed.
+    fn choices() -> Vec<Self>;
+    fn label(&self) -> String;
 }
 
 #[derive(Clone)]
 pub struct Title {
     pub name: String,
     pub color: Color,
-    pub font_size: f32
+    pub font_size: f32,
+    ///Translation key looked up in the current language, see `i18n`. **name** is shown when it has no translation.
+    pub key: Option<String>,
 }
 impl Title {
+    ///Return the text shown for this ´Title´ in the current language.
+    pub fn text(&self) -> String {
+        self.key.as_deref()
+            .and_then(i18n::translate)
+            .unwrap_or_else(|| self.name.clone())
+    }
+
     ///Return the size needed to draw this ´Title´ on the screen.
     fn size(&self) -> Vec2 {
-        let text_size = measure_text(&self.name, None, self.font_size as u16, 1.0);
+        let text_size = measure_text(&self.text(), self.font_size as u16);
         Vec2{
             x: text_size.width,
             y: text_size.height,
@@ -35,31 +135,67 @@ impl Title {
     }
 }
 
-#[derive(Clone)]
 pub struct Element<T: MenuElement + ?Sized> {
     id: usize,
+    name: Option<String>,
+    owned: bool,
     data: Rc<RefCell<T>>,
+    // Set by **edit**, shared by every handle to the element, so its menu knows to lay itself out again.
+    dirty: Rc<Cell<bool>>,
+    // The bounding rectangle the menu laid the element out with, only used in the menu's own handle.
+    rect: Cell<Option<Rect>>,
+    type_name: &'static str,
+    // Shared by every handle to the element, like `dirty`.
+    tooltip: SharedTooltip,
 }
 impl<T: MenuElement + ?Sized> Element<T> {
     ///Return a immutable reference to the element.
     pub fn read(&self) -> Ref<T> {
         self.data.deref().borrow()
     }
-    ///Return a mutable reference to the element.
+    ///Return a mutable reference to the element, which its menu lays out again before the next draw.
     pub fn edit(&self) -> RefMut<T> {
+        self.dirty.set(true);
         self.data.deref().borrow_mut()
     }
 
-
-    //pub fn clone(&self) -> Element<T> {
-    //    Element{
-    //        id: self.id,
-    //        data: self.data.clone(),
-    //    }
-    //}
+    ///Return the id given to the element by its menu, unique within that menu.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+    ///Return the name the element was added with, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    ///Return the full name of the element's type, e.g. `Simples_menu::Button`, also for elements of a `dyn MenuElement`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    ///Show `tooltip` while the pointer rests on the element, see the `tooltip` module.
+    pub fn set_tooltip(&self, tooltip: impl Into<Tooltip>) {
+        *self.tooltip.deref().borrow_mut() = Some(tooltip.into());
+    }
+    ///Stop showing a tooltip for the element.
+    pub fn remove_tooltip(&self) {
+        *self.tooltip.deref().borrow_mut() = None;
+    }
+}
+impl<T: MenuElement + ?Sized> Clone for Element<T> {
+    fn clone(&self) -> Element<T> {
+        Element{
+            id: self.id,
+            name: self.name.clone(),
+            owned: self.owned,
+            data: self.data.clone(),
+            dirty: self.dirty.clone(),
+            rect: self.rect.clone(),
+            type_name: self.type_name,
+            tooltip: self.tooltip.clone(),
+        }
+    }
 }
 
-#[derive(BoundingRect)]
+#[derive(BoundingRect, Clickable)]
 pub struct Button {
     pub title: Title,
     pub visible: bool,
@@ -70,8 +206,22 @@ pub struct Button {
     pub is_pressed: bool,
 
     pub has_been_pressed: bool,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
+    ///Whether the button is clicked when pressed or when released over it.
+    pub activation: Activation,
+    ///Set for one update when the button is right-clicked.
+    pub right_clicked: bool,
+    ///Set for one update when the button is middle-clicked.
+    pub middle_clicked: bool,
+    ///Set for one update when the button is double-clicked or double-tapped.
+    pub double_clicked: bool,
+    ///Set for one update when a finger or the mouse has been held on the button for `input::LONG_PRESS_TIME`.
+    pub long_pressed: bool,
 
     visible_color: Color,
+    // Set while the size follows the title.
+    auto_size: Option<AutoSize>,
     // other properties specific to buttons
 }
 impl Button {
@@ -81,6 +231,7 @@ impl Button {
             name: lable,
             color: WHITE,
             font_size: 13.0,
+            key: None,
         };
 
         let real_size = size.unwrap_or(label_title.size() + Vec2{ x: 10.0, y: 10.0 });
@@ -97,227 +248,157 @@ impl Button {
                 }
             }
         }
+        let auto_size = size.is_none().then(|| AutoSize::new(position_type, &label_title, real_size));
         Button{
             size: real_size,
             is_pressed: false,
             title: label_title,
             visible: true,
             color: GRAY,
+            enabled: true,
+            disabled_reason: None,
+            activation: Activation::OnRelease,
+            right_clicked: false,
+            middle_clicked: false,
+            double_clicked: false,
+            long_pressed: false,
             visible_color: GRAY,
             position: real_position,
             has_been_pressed: false,
+            auto_size,
         }
     }
 }
 impl MenuElement for Button {
     fn update(&mut self, menu_position: Vec2) {
-        self.is_pressed = false;
-        self.has_been_pressed = false;
-        self.visible_color = self.color;
-
-        let button_position = self.position + menu_position;
-        let button_rect = Rect{
-            x: button_position.x,
-            y: button_position.y,
-            w: self.size.x,
-            h: self.size.y,
-        };
-
-        let mouse_posi:Vec2;
-        {
-            let mouse_flot = mouse_position();
-            mouse_posi = Vec2{ x: mouse_flot.0, y: mouse_flot.1 }
-        }
-        if button_rect.contains(mouse_posi) {
-            self.visible_color = Color{
-                r: self.color.r - 0.1,
-                g: self.color.g - 0.1,
-                b: self.color.b - 0.1,
-                a: self.color.a,
-            };
-
-            if is_mouse_button_pressed(MouseButton::Left) {
-                self.has_been_pressed = true;
-            }
-            if is_mouse_button_down(MouseButton::Left) {
-                self.is_pressed = true;
-            }
+        if self.auto_size.as_mut().is_some_and(|auto_size| !auto_size.fit(&self.title, &mut self.position, &mut self.size)) {
+            self.auto_size = None;
         }
+        let interaction = self.update_interaction(menu_position);
+        self.right_clicked = interaction.right_clicked;
+        self.middle_clicked = interaction.middle_clicked;
+        self.double_clicked = interaction.double_clicked;
+        self.long_pressed = interaction.long_pressed;
     }
 
     fn draw(&self, start_position: Vec2) {
-        let position = self.position + start_position;
-
-        draw_rectangle(position.x,
-                       position.y,
-                       self.size.x,
-                       self.size.y,
-                       self.visible_color);
-
-        let text_size = measure_text(&self.title.name, None, self.title.font_size as u16, 1.0);
-        draw_text(&self.title.name,
-                  position.x + (self.size.x - text_size.width) / 2.0,
-                  position.y + (self.size.y + text_size.height) / 2.0,
-                  self.title.font_size,
-                  self.title.color)
+        if !self.visible {return}
+        let text_color = if self.enabled { self.title.color } else { disabled_colors().text };
+        draw_titled_rect(self.position + start_position, self.size, self.visible_color, &self.title, text_color)
     }
     
     fn bounding_rect(&self) -> Option<Rect>{
         self.bounding_rect()
     }
-}
 
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: Some(self.size),
+            color: Some(self.color),
+            title: Some(self.title.name.clone()),
+            visible: Some(self.visible),
+            enabled: Some(self.enabled),
+        }
+    }
 
-//#[derive(BoundingRect)]
-pub struct SmartButton<T> {
-    pub title: Title,
-    pub visible: bool,
-    pub color: Color,
-    pub position: Vec2,
-    pub size: Vec2,
+    fn set_properties(&mut self, properties: &Properties) {
+        // A size set by hand stops following the title.
+        if properties.size.is_some_and(|size| size != self.size) {
+            self.auto_size = None;
+        }
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.size, &properties.size);
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.title.name, &properties.title);
+        set_if(&mut self.visible, &properties.visible);
+        set_if(&mut self.enabled, &properties.enabled);
+    }
 
-    pub is_pressed: bool,
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+}
 
-    pub has_been_pressed: bool,
-    pub action: Option<fn(T)>,
 
-    visible_color: Color,
-    // other properties specific to buttons
+///A `Button` with an action to run when it is clicked, see **smart_action**.
+///
+///The fields of the button are reached through it, like `smart_button.title`.
+pub struct SmartButton<T> {
+    pub button: Button,
+    pub action: Option<fn(T)>,
 }
 impl<T> SmartButton<T> {
     ///Create a new button with the default arguments.
     pub fn new(lable: String, position_type: PositionType, position: Vec2, size: Option<Vec2>, action: fn(T)) -> SmartButton<T> {
-        let label_title = Title {
-            name: lable,
-            color: WHITE,
-            font_size: 13.0,
-        };
-
-        let real_size = size.unwrap_or(label_title.size() + Vec2{ x: 10.0, y: 10.0 });
-
-        let real_position: Vec2;
-        match position_type {
-            TopLeft => {
-                real_position = position
-            }
-            Center => {
-                real_position = Vec2{
-                    x: position.x - (real_size.x / 2.0),
-                    y: position.y - (real_size.y / 2.0),
-                }
-            }
-        }
         SmartButton {
-            size: real_size,
-            is_pressed: false,
-            title: label_title,
-            visible: true,
-            color: GRAY,
-            visible_color: GRAY,
-            position: real_position,
+            button: Button::new(lable, position_type, position, size),
             action: Some(action),
-            has_been_pressed: false,
         }
     }
 
 
-    pub fn smart_action(&self, menu: Menu, data: T) {
-        // A little hack to get the rect of the button
-        let (menu_rect, menu_tile_rect) = menu.calculate_menu_rect();
-        let menu_position = vec2(menu_rect.x, menu_rect.y + menu_tile_rect.h);
-        let button_position = self.position + menu_position;
-        let button_rect = Rect{
-            x: button_position.x,
-            y: button_position.y,
-            w: self.size.x,
-            h: self.size.y,
-        };
-        let mouse_posi:Vec2;
-        {
-            let mouse_flot = mouse_position();
-            mouse_posi = Vec2{ x: mouse_flot.0, y: mouse_flot.1 }
-        }
-        // A little hack to get the rect of the button
-
-        if button_rect.contains(mouse_posi) {
-
-            if is_mouse_button_pressed(MouseButton::Left) {
-                if let Some(action) = self.action {
-                    action(data);
-                }
+    ///Run the action with `data` if the button was clicked on the last update, on press or on
+    ///release as set by **activation**.
+    pub fn smart_action(&self, data: T) {
+        if self.has_been_pressed {
+            if let Some(action) = self.action {
+                action(data);
             }
         }
     }
 }
+impl<T> Deref for SmartButton<T> {
+    type Target = Button;
+
+    fn deref(&self) -> &Button {
+        &self.button
+    }
+}
+impl<T> DerefMut for SmartButton<T> {
+    fn deref_mut(&mut self) -> &mut Button {
+        &mut self.button
+    }
+}
 impl<T> MenuElement for SmartButton<T> {
     fn update(&mut self, menu_position: Vec2) {
-        self.is_pressed = false;
-        self.has_been_pressed = false;
-        self.visible_color = self.color;
-
-        let button_position = self.position + menu_position;
-        let button_rect = Rect{
-            x: button_position.x,
-            y: button_position.y,
-            w: self.size.x,
-            h: self.size.y,
-        };
-
-        let mouse_posi:Vec2;
-        {
-            let mouse_flot = mouse_position();
-            mouse_posi = Vec2{ x: mouse_flot.0, y: mouse_flot.1 }
-        }
-        if button_rect.contains(mouse_posi) {
-            self.visible_color = Color{
-                r: self.color.r - 0.1,
-                g: self.color.g - 0.1,
-                b: self.color.b - 0.1,
-                a: self.color.a,
-            };
-
-            if is_mouse_button_pressed(MouseButton::Left) {
-                self.has_been_pressed = true;
-            }
-            if is_mouse_button_down(MouseButton::Left) {
-                self.is_pressed = true;
-            }
-        }
+        self.button.update(menu_position)
     }
 
-
     fn draw(&self, start_position: Vec2) {
-        let position = self.position + start_position;
+        self.button.draw(start_position)
+    }
 
-        draw_rectangle(position.x,
-                       position.y,
-                       self.size.x,
-                       self.size.y,
-                       self.visible_color);
+    fn bounding_rect(&self) -> Option<Rect>{
+        MenuElement::bounding_rect(&self.button)
+    }
 
-        let text_size = measure_text(&self.title.name, None, self.title.font_size as u16, 1.0);
-        draw_text(&self.title.name,
-                  position.x + (self.size.x - text_size.width) / 2.0,
-                  position.y + (self.size.y + text_size.height) / 2.0,
-                  self.title.font_size,
-                  self.title.color)
+    fn properties(&self) -> Properties {
+        self.button.properties()
     }
 
-    fn bounding_rect(&self) -> Option<Rect>{
-        self.bounding_rect()
+    fn set_properties(&mut self, properties: &Properties) {
+        self.button.set_properties(properties)
+    }
+
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(&self.button)
     }
 }
 
 
-#[derive(BoundingRect)]
+#[derive(BoundingRect, Clickable)]
 pub struct CheckBox {
     pub visible: bool,
     pub position: Vec2,
     pub size: Vec2,
     pub is_checked: bool,
     pub color: Color,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
 
     visible_color: Color,
+    binding: Option<Bound<bool>>,
 }
 impl CheckBox {
     pub fn new(position_type: PositionType, position: Vec2, size: Vec2) -> CheckBox {
@@ -339,42 +420,38 @@ impl CheckBox {
             size,
             is_checked: false,
             color: GRAY,
+            enabled: true,
+            disabled_reason: None,
 
             visible_color: LIGHTGRAY,
+            binding: None,
         }
     }
+
+    ///Bind **is_checked** to shared state, taking its current value.
+    pub fn bind<B: Binding<bool> + 'static>(&mut self, binding: B) {
+        let bound = Bound::new(binding);
+        self.is_checked = bound.value();
+        self.binding = Some(bound);
+    }
 }
 impl MenuElement for CheckBox {
     fn update(&mut self, menu_position: Vec2) {
-        self.visible_color = self.color;
-        let position = self.position + menu_position;
-        let check_box_rect = Rect{
-            x: position.x,
-            y: position.y,
-            w: self.size.x,
-            h: self.size.y,
-        };
+        if let Some(binding) = &mut self.binding {
+            binding.pull(&mut self.is_checked);
+        }
 
-        let mouse_posi:Vec2;
-        {
-            let mouse_flot = mouse_position();
-            mouse_posi = Vec2{ x: mouse_flot.0, y: mouse_flot.1 }
-        }
-        if check_box_rect.contains(mouse_posi) {
-            self.visible_color = Color {
-                r: self.color.r - 0.1,
-                g: self.color.g - 0.1,
-                b: self.color.b - 0.1,
-                a: self.color.a,
-            };
+        if self.update_interaction(menu_position).clicked {
+            self.is_checked = !self.is_checked;
+        }
 
-            if is_mouse_button_pressed(MouseButton::Left) {
-                self.is_checked = !self.is_checked;
-            }
+        if let Some(binding) = &mut self.binding {
+            binding.push(&self.is_checked);
         }
     }
 
     fn draw(&self, menu_position: Vec2) {
+        if !self.visible {return}
         let position = self.position + menu_position;
         let check_box_rect = Rect{
             x: position.x,
@@ -398,13 +475,47 @@ impl MenuElement for CheckBox {
                        self.visible_color
         );
         if self.is_checked {
-            draw_circle(center.x, center.y, (radius / 2.0) - (radius / 10.0), GREEN)
+            let mark_color = if self.enabled { GREEN } else { disabled_colors().text };
+            draw_circle(center.x, center.y, (radius / 2.0) - (radius / 10.0), mark_color)
         }
     }
 
     fn bounding_rect(&self) -> Option<Rect> {
         self.bounding_rect()
     }
+
+    fn save_state(&self, key: &str, state: &mut MenuState) {
+        state.set_bool(key, self.is_checked);
+    }
+
+    fn load_state(&mut self, key: &str, state: &MenuState) {
+        if let Some(is_checked) = state.get_bool(key) {
+            self.is_checked = is_checked;
+        }
+    }
+
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: Some(self.size),
+            color: Some(self.color),
+            title: None,
+            visible: Some(self.visible),
+            enabled: Some(self.enabled),
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.size, &properties.size);
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.visible, &properties.visible);
+        set_if(&mut self.enabled, &properties.enabled);
+    }
+
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
 }
 
 #[derive(BoundingRect)]
@@ -414,6 +525,8 @@ pub struct TextLabel {
     pub position: Vec2,
 
     size: Vec2,
+    // The title text, font size and scale the size was measured for.
+    measured: (String, f32, f32),
 }
 impl TextLabel {
     ///Create a new text label with the default arguments.
@@ -422,6 +535,7 @@ impl TextLabel {
             name: lable,
             color: WHITE,
             font_size: 13.0,
+            key: None,
         };
         let size = label_title.size();
         let real_position: Vec2;
@@ -436,23 +550,74 @@ impl TextLabel {
                 }
             }
         }
+        let measured = (label_title.text(), label_title.font_size, screen_scale());
         TextLabel {
             size,
+            measured,
             title: label_title,
             visible: true,
             position: real_position,
         }
     }
 }
+///Keeps a button created without a size fitted to its title, e.g. after the language changed.
+struct AutoSize {
+    // Where the button was anchored, a centered one stays centered as it grows.
+    anchor: PositionType,
+    // What the size was last fitted to.
+    text: String,
+    font_size: f32,
+    scale: f32,
+    size: Vec2,
+}
+impl AutoSize {
+    fn new(anchor: PositionType, title: &Title, size: Vec2) -> AutoSize {
+        AutoSize{
+            anchor,
+            text: title.text(),
+            font_size: title.font_size,
+            scale: screen_scale(),
+            size,
+        }
+    }
+
+    ///Resize the button when its title text, font size or scale changed. Return `false`, leaving
+    ///the button alone, once its size was set by hand.
+    fn fit(&mut self, title: &Title, position: &mut Vec2, size: &mut Vec2) -> bool {
+        if *size != self.size {
+            return false;
+        }
+        let text = title.text();
+        let scale = screen_scale();
+        if text == self.text && title.font_size == self.font_size && scale == self.scale {
+            return true;
+        }
+
+        let new_size = title.size() + Vec2{ x: 10.0, y: 10.0 };
+        if self.anchor == Center {
+            *position -= (new_size - *size) / 2.0;
+        }
+        *size = new_size;
+        *self = AutoSize{ anchor: self.anchor, text, font_size: title.font_size, scale, size: new_size };
+        true
+    }
+}
+
 impl MenuElement for TextLabel {
     fn update(&mut self, menu_position: Vec2) {
-        self.size = self.title.size();
+        // Only measured again when the title or the scale changed.
+        let measured = (self.title.text(), self.title.font_size, screen_scale());
+        if measured != self.measured {
+            self.size = self.title.size();
+            self.measured = measured;
+        }
     }
 
     fn draw(&self, start_position: Vec2) {
+        if !self.visible {return}
         let position = self.position + start_position;
 
-        draw_text(&self.title.name,
+        draw_text(&self.title.text(),
                   position.x,
                   position.y,
                   self.title.font_size,
@@ -462,83 +627,893 @@ impl MenuElement for TextLabel {
     fn bounding_rect(&self) -> Option<Rect> {
         self.bounding_rect()
     }
+
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: None,
+            color: None,
+            title: Some(self.title.name.clone()),
+            visible: Some(self.visible),
+            enabled: None,
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.title.name, &properties.title);
+        set_if(&mut self.visible, &properties.visible);
+    }
 }
 
-//#[derive(Clone)]
-pub struct Menu {
-    pub title: Title,
+#[derive(BoundingRect, Clickable)]
+pub struct Slider {
     pub visible: bool,
-    pub color: Color,
-    pub edge: f32,
     pub position: Vec2,
-    pub size: Option<Vec2>,
-    pub elements: Vec<Element<dyn MenuElement>>,
+    pub size: Vec2,
+    pub min: f32,
+    pub max: f32,
+    pub value: f32,
+    ///When set, the value snaps to multiples of this step.
+    pub step: Option<f32>,
+    pub color: Color,
+    pub handle_color: Color,
+
+    pub is_dragging: bool,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
 
     visible_color: Color,
+    drag_pointer: Option<u64>,
+    binding: Option<Bound<f32>>,
 }
-impl Menu {
-    ///Create a new menu with the default arguments.
-    pub fn new(name: String, position: Vec2) -> Menu {
-        Menu{
-            title: Title {
-                name: name,
-                color: WHITE,
-                font_size: 25.0,
-            },
+impl Slider {
+    ///Create a new slider with the default arguments.
+    pub fn new(position_type: PositionType, position: Vec2, size: Vec2, min: f32, max: f32, value: f32) -> Slider {
+        let real_position: Vec2;
+        match position_type {
+            TopLeft => {
+                real_position = position
+            }
+            Center => {
+                real_position = Vec2{
+                    x: position.x - (size.x / 2.0),
+                    y: position.y - (size.y / 2.0),
+                }
+            }
+        }
+        Slider{
             visible: true,
-            color: DARKGRAY,
-            edge: 20.0,
-            position: position,
-            size: None,
-            elements: vec![],
+            position: real_position,
+            size,
+            min,
+            max,
+            value: value.clamp(min, max),
+            step: None,
+            color: GRAY,
+            handle_color: LIGHTGRAY,
+            is_dragging: false,
+            drag_pointer: None,
+            enabled: true,
+            disabled_reason: None,
 
-            visible_color: DARKGRAY,
+            visible_color: GRAY,
+            binding: None,
         }
     }
-    ///Adds a new menu element to the **menu.elements** vector,
-    ///and return a Element object that can be edited later.
-    pub fn add_element<T: MenuElement + 'static>(&mut self, element: T) ->  Element<T> {
-        let element_ref = Rc::new(RefCell::new(element));
-        let element = Element{
-            id: self.elements.len(),
-            data: element_ref.clone(),
-        };
-        self.elements.push(Element { id: self.elements.len(), data: element_ref });
 
-        element
+    ///Bind **value** to shared state, taking its current value.
+    pub fn bind<B: Binding<f32> + 'static>(&mut self, binding: B) {
+        let bound = Bound::new(binding);
+        self.value = bound.value();
+        self.binding = Some(bound);
     }
 
-    fn calculate_menu_rect(&self) -> (Rect, Rect) {
-        // Calculate the bounding rectangle for the menu.
-        let mut elements_rect = Rect::new(0.0, 0.0, 0.0, 0.0);
+    ///Return where the value sits between **min** and **max**, from `0.0` to `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        (self.value - self.min) / (self.max - self.min)
+    }
 
-        // Iterate over each element in the menu.
-        for element in &self.elements {
-            // Get the bounding rectangle for the element.
-            if let Some(rect) = element.read().bounding_rect() {
-                // Combine the element rectangle with the overall menu rectangle.
-                elements_rect = elements_rect.combine_with(rect);
+    fn set_fraction(&mut self, fraction: f32) {
+        let mut value = self.min + fraction.clamp(0.0, 1.0) * (self.max - self.min);
+        if let Some(step) = self.step {
+            if step > 0.0 {
+                value = self.min + ((value - self.min) / step).round() * step;
             }
         }
+        self.value = value.clamp(self.min, self.max);
+    }
+}
+impl MenuElement for Slider {
+    fn update(&mut self, menu_position: Vec2) {
+        if let Some(binding) = &mut self.binding {
+            binding.pull(&mut self.value);
+            self.value = self.value.clamp(self.min, self.max);
+        }
 
-        // Get the size of the menu title text.
-        let text_size = measure_text(
-            &self.title.name,
-            None,
-            self.title.font_size as u16,
-            1.0
-        );
+        self.update_interaction(menu_position);
+        let slider_rect = self.hit_rect(menu_position);
+        let pointers = pointers();
+        // Follow the pointer that went down on the slider, whichever other fingers are on the screen.
+        if let Some(pointer) = pointers.iter().find(|pointer| self.enabled && pointer.pressed && slider_rect.contains(pointer.position)) {
+            self.drag_pointer = Some(pointer.id);
+            input::capture(pointer.id);
+        }
+        if !self.enabled {
+            self.drag_pointer = None;
+        }
+        let dragged_by = self.drag_pointer.and_then(|id| pointers.iter().find(|pointer| pointer.id == id && pointer.down));
+        match dragged_by {
+            Some(pointer) => self.set_fraction((pointer.position.x - slider_rect.x) / slider_rect.w),
+            None => self.drag_pointer = None,
+        }
+        self.is_dragging = self.drag_pointer.is_some();
 
-        // Create a rectangle for the menu title.
-        let title_rect = Rect {
-            x: elements_rect.x + elements_rect.w / 2.0,
-            y: elements_rect.y,
-            w: text_size.width,
-            h: text_size.height,
-        };
+        if let Some(binding) = &mut self.binding {
+            binding.push(&self.value);
+        }
+    }
 
-        // Combine the menu title rectangle with the overall menu rectangle.
-        elements_rect = elements_rect.combine_with(title_rect);
+    fn draw(&self, menu_position: Vec2) {
+        if !self.visible {return}
+        let position = self.position + menu_position;
+
+        draw_rectangle(position.x,
+                       position.y,
+                       self.size.x,
+                       self.size.y,
+                       self.visible_color);
+
+        let handle_width = self.size.y / 2.0;
+        let handle_x = position.x + (self.size.x - handle_width) * self.fraction();
+        let handle_color = if self.enabled { self.handle_color } else { disabled_colors().text };
+        draw_rectangle(handle_x,
+                       position.y,
+                       handle_width,
+                       self.size.y,
+                       handle_color);
+    }
+
+    fn bounding_rect(&self) -> Option<Rect> {
+        self.bounding_rect()
+    }
+
+    fn save_state(&self, key: &str, state: &mut MenuState) {
+        state.set_f32(key, self.value);
+    }
+
+    fn load_state(&mut self, key: &str, state: &MenuState) {
+        if let Some(value) = state.get_f32(key) {
+            self.value = value.clamp(self.min, self.max);
+        }
+    }
+
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: Some(self.size),
+            color: Some(self.color),
+            title: None,
+            visible: Some(self.visible),
+            enabled: Some(self.enabled),
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.size, &properties.size);
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.visible, &properties.visible);
+        set_if(&mut self.enabled, &properties.enabled);
+    }
+
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+}
+
+#[derive(BoundingRect, Clickable)]
+pub struct TextInput {
+    pub visible: bool,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub text: String,
+    pub text_color: Color,
+    pub font_size: f32,
+    pub color: Color,
+    ///Only accept characters that can be part of a number.
+    pub numeric: bool,
+    pub max_length: Option<usize>,
+
+    pub is_focused: bool,
+    ///Set on the frame the text was changed by the user.
+    pub has_changed: bool,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
+
+    visible_color: Color,
+    binding: Option<Bound<String>>,
+    // Seconds since the input was focused, the caret blinks every half of one.
+    caret_time: f32,
+}
+impl TextInput {
+    ///Create a new text input with the default arguments.
+    pub fn new(position_type: PositionType, position: Vec2, size: Vec2) -> TextInput {
+        let real_position: Vec2;
+        match position_type {
+            TopLeft => {
+                real_position = position
+            }
+            Center => {
+                real_position = Vec2{
+                    x: position.x - (size.x / 2.0),
+                    y: position.y - (size.y / 2.0),
+                }
+            }
+        }
+        TextInput{
+            visible: true,
+            position: real_position,
+            size,
+            text: String::new(),
+            text_color: WHITE,
+            font_size: 13.0,
+            color: GRAY,
+            numeric: false,
+            max_length: None,
+            is_focused: false,
+            has_changed: false,
+            enabled: true,
+            disabled_reason: None,
+
+            visible_color: GRAY,
+            binding: None,
+            caret_time: 0.0,
+        }
+    }
+
+    ///Bind **text** to shared state, taking its current value.
+    pub fn bind<B: Binding<String> + 'static>(&mut self, binding: B) {
+        let bound = Bound::new(binding);
+        self.text = bound.value();
+        self.binding = Some(bound);
+    }
+
+    fn read_keyboard(&mut self) {
+        // In order, so a character typed after Backspace isn't the one erased.
+        for event in input::key_events() {
+            match event {
+                KeyEvent::Char(character) if self.accepts(character) => {
+                    self.text.push(character);
+                    self.has_changed = true;
+                }
+                KeyEvent::Key(KeyCode::Backspace) => {
+                    self.has_changed |= self.text.pop().is_some();
+                }
+                KeyEvent::Key(KeyCode::Enter | KeyCode::Escape) => {
+                    self.is_focused = false;
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn accepts(&self, character: char) -> bool {
+        if character.is_control() {
+            return false;
+        }
+        if let Some(max_length) = self.max_length {
+            if self.text.chars().count() >= max_length {
+                return false;
+            }
+        }
+        !self.numeric || character.is_ascii_digit() || matches!(character, '.' | '-' | '+' | 'e' | 'E')
+    }
+}
+impl MenuElement for TextInput {
+    fn update(&mut self, menu_position: Vec2) {
+        self.has_changed = false;
+        if let Some(binding) = &mut self.binding {
+            binding.pull(&mut self.text);
+        }
+
+        let interaction = self.update_interaction(menu_position);
+        if pointer_pressed() {
+            self.is_focused = interaction.hovered;
+            self.caret_time = 0.0;
+        }
+        // A disabled input can't be focused, and loses focus when disabled while typing.
+        self.is_focused &= self.enabled;
+
+        if self.is_focused {
+            self.caret_time += input::frame_time();
+            self.read_keyboard();
+        }
+
+        if let Some(binding) = &mut self.binding {
+            binding.push(&self.text);
+        }
+    }
+
+    fn draw(&self, menu_position: Vec2) {
+        if !self.visible {return}
+        let position = self.position + menu_position;
+
+        draw_rectangle(position.x,
+                       position.y,
+                       self.size.x,
+                       self.size.y,
+                       self.visible_color);
+
+        let text_color = if self.enabled { self.text_color } else { disabled_colors().text };
+        let text_size = measure_text(&self.text, self.font_size as u16);
+        let text_position = vec2(position.x + 5.0, position.y + (self.size.y + text_size.height) / 2.0);
+        draw_text(&self.text,
+                  text_position.x,
+                  text_position.y,
+                  self.font_size,
+                  text_color);
+
+        // blinking caret after the text
+        if self.is_focused && self.caret_time.fract() < 0.5 {
+            draw_rectangle(text_position.x + text_size.width + 1.0,
+                           position.y + 3.0,
+                           1.0,
+                           self.size.y - 6.0,
+                           self.text_color);
+        }
+    }
+
+    fn bounding_rect(&self) -> Option<Rect> {
+        self.bounding_rect()
+    }
+
+    fn save_state(&self, key: &str, state: &mut MenuState) {
+        state.set_text(key, &self.text);
+    }
+
+    fn load_state(&mut self, key: &str, state: &MenuState) {
+        if let Some(text) = state.get_text(key) {
+            self.text = text.to_string();
+        }
+    }
+
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: Some(self.size),
+            color: Some(self.color),
+            title: None,
+            visible: Some(self.visible),
+            enabled: Some(self.enabled),
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.size, &properties.size);
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.visible, &properties.visible);
+        set_if(&mut self.enabled, &properties.enabled);
+    }
+
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+}
+
+#[derive(BoundingRect, Clickable)]
+pub struct Dropdown {
+    pub visible: bool,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub options: Vec<String>,
+    pub selected: usize,
+    pub text_color: Color,
+    pub font_size: f32,
+    pub color: Color,
+
+    pub is_open: bool,
+    ///Set on the frame a new option was selected.
+    pub has_changed: bool,
+    pub enabled: bool,
+    pub disabled_reason: Option<String>,
+
+    visible_color: Color,
+    hovered_option: Option<usize>,
+    binding: Option<Bound<usize>>,
+}
+impl Dropdown {
+    ///Create a new dropdown, sized to fit its longest option if no size is given.
+    pub fn new(position_type: PositionType, position: Vec2, size: Option<Vec2>, options: Vec<String>) -> Dropdown {
+        let font_size = 13.0;
+        let real_size = size.unwrap_or_else(|| {
+            let widest = options.iter()
+                .map(|option| measure_text(option, font_size as u16))
+                .fold(Vec2::ZERO, |size, text| size.max(vec2(text.width, text.height)));
+            widest + Vec2{ x: 30.0, y: 10.0 }
+        });
+
+        let real_position: Vec2;
+        match position_type {
+            TopLeft => {
+                real_position = position
+            }
+            Center => {
+                real_position = Vec2{
+                    x: position.x - (real_size.x / 2.0),
+                    y: position.y - (real_size.y / 2.0),
+                }
+            }
+        }
+        Dropdown{
+            visible: true,
+            position: real_position,
+            size: real_size,
+            options,
+            selected: 0,
+            text_color: WHITE,
+            font_size,
+            color: GRAY,
+            is_open: false,
+            has_changed: false,
+            enabled: true,
+            disabled_reason: None,
+
+            visible_color: GRAY,
+            hovered_option: None,
+            binding: None,
+        }
+    }
+
+    ///Bind the **selected** index to shared state, taking its current value.
+    pub fn bind<B: Binding<usize> + 'static>(&mut self, binding: B) {
+        let bound = Bound::new(binding);
+        self.selected = bound.value();
+        self.binding = Some(bound);
+    }
+
+    ///Return the text of the selected option.
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+
+    fn option_rect(&self, position: Vec2, index: usize) -> Rect {
+        Rect{
+            x: position.x,
+            y: position.y + self.size.y * (index + 1) as f32,
+            w: self.size.x,
+            h: self.size.y,
+        }
+    }
+
+    fn draw_option(&self, rect: Rect, text: &str, color: Color) {
+        draw_dropdown_option(rect, text, color, self.font_size, self.text_color());
+    }
+
+    fn text_color(&self) -> Color {
+        if self.enabled { self.text_color } else { disabled_colors().text }
+    }
+}
+impl MenuElement for Dropdown {
+    fn update(&mut self, menu_position: Vec2) {
+        self.has_changed = false;
+        self.hovered_option = None;
+        if let Some(binding) = &mut self.binding {
+            binding.pull(&mut self.selected);
+        }
+
+        let position = self.position + menu_position;
+        if self.is_open {
+            let mouse_posi = pointer_position();
+            self.hovered_option = (0..self.options.len())
+                .find(|index| self.option_rect(position, *index).contains(mouse_posi));
+        }
+
+        let interaction = self.update_interaction(menu_position);
+        if !self.enabled {
+            self.is_open = false;
+        } else if interaction.clicked {
+            self.is_open = !self.is_open;
+        } else if pointer_pressed() && self.is_open && !interaction.hovered {
+            if let Some(index) = self.hovered_option {
+                self.has_changed = index != self.selected;
+                self.selected = index;
+            }
+            self.is_open = false;
+        }
+
+        if let Some(binding) = &mut self.binding {
+            binding.push(&self.selected);
+        }
+    }
+
+    fn draw(&self, menu_position: Vec2) {
+        if !self.visible {return}
+        let position = self.position + menu_position;
+        let dropdown_rect = Rect{
+            x: position.x,
+            y: position.y,
+            w: self.size.x,
+            h: self.size.y,
+        };
+
+        self.draw_option(dropdown_rect, self.selected_option().unwrap_or(""), self.visible_color);
+        // the arrow on the right side
+        let arrow = if self.is_open { "^" } else { "v" };
+        let arrow_size = measure_text(arrow, self.font_size as u16);
+        draw_text(arrow,
+                  position.x + self.size.x - arrow_size.width - 5.0,
+                  position.y + (self.size.y + arrow_size.height) / 2.0,
+                  self.font_size,
+                  self.text_color());
+
+        // Over the elements after it in the menu.
+        if self.is_open {
+            let options: Vec<(Rect, String, Color)> = self.options.iter().enumerate()
+                .map(|(index, option)| {
+                    let color = if self.hovered_option == Some(index) { self.color } else { DARKGRAY };
+                    (self.option_rect(position, index), option.clone(), color)
+                })
+                .collect();
+            let (font_size, text_color) = (self.font_size, self.text_color());
+            draw_later(move || {
+                for (rect, option, color) in &options {
+                    draw_dropdown_option(*rect, option, *color, font_size, text_color);
+                }
+            });
+        }
+    }
+
+    // Only the closed box, the open list lies over the elements under it without moving them.
+    fn bounding_rect(&self) -> Option<Rect> {
+        self.bounding_rect()
+    }
+
+    fn overlay_rect(&self) -> Option<Rect> {
+        if !self.is_open || !self.visible || self.options.is_empty() {
+            return None;
+        }
+        let first = self.option_rect(self.position, 0);
+        Some(Rect{ h: first.h * self.options.len() as f32, ..first })
+    }
+
+    // The option text is saved rather than its index, so reordering the options keeps the choice.
+    fn save_state(&self, key: &str, state: &mut MenuState) {
+        if let Some(option) = self.selected_option() {
+            state.set_text(key, option);
+        }
+    }
+
+    fn load_state(&mut self, key: &str, state: &MenuState) {
+        let saved = state.get_text(key);
+        if let Some(index) = self.options.iter().position(|option| Some(option.as_str()) == saved) {
+            self.selected = index;
+        }
+    }
+
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            size: Some(self.size),
+            color: Some(self.color),
+            title: None,
+            visible: Some(self.visible),
+            enabled: Some(self.enabled),
+        }
+    }
+
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        set_if(&mut self.size, &properties.size);
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.visible, &properties.visible);
+        set_if(&mut self.enabled, &properties.enabled);
+    }
+
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+}
+
+fn draw_dropdown_option(rect: Rect, text: &str, color: Color, font_size: f32, text_color: Color) {
+    draw_rectangle(rect.x, rect.y, rect.w, rect.h, color);
+
+    let text_size = measure_text(text, font_size as u16);
+    draw_text(text,
+              rect.x + 5.0,
+              rect.y + (rect.h + text_size.height) / 2.0,
+              font_size,
+              text_color);
+}
+
+//#[derive(Clone)]
+pub struct Menu {
+    pub title: Title,
+    pub visible: bool,
+    pub color: Color,
+    pub edge: f32,
+    pub position: Vec2,
+    pub size: Option<Vec2>,
+    pub elements: Vec<Element<dyn MenuElement>>,
+    pub opacity: f32,
+    pub animator: Animator,
+    ///A collapsed menu only shows its title.
+    pub collapsed: bool,
+    ///Let the user collapse the menu by clicking its title.
+    pub collapsible: bool,
+    ///Scale the menu around its position, on top of the global `ui_scale`.
+    pub scale: f32,
+    ///Let a menu with a **size** scroll through elements that don't fit in it, by dragging with a finger.
+    pub scrollable: bool,
+    ///How far the elements of a scrollable menu are scrolled.
+    pub scroll: Vec2,
+
+    visible_color: Color,
+    next_id: usize,
+    offset: Vec2,
+    opacity_tween: Option<Tween<f32>>,
+    offset_tween: Option<Tween<Vec2>>,
+    hiding: bool,
+    input: PointerTracker,
+    events: Vec<MenuEvent>,
+    // The id of the element under the pointer on the last update, whose tooltip is shown.
+    hovered: Option<usize>,
+    // The id of the element drawing above the others on the last update, with the area it covers.
+    overlay: Option<(usize, Rect)>,
+    // How long the pointer has rested on that element, and whether a click hid its tooltip since.
+    hover_time: f32,
+    tooltip_dismissed: bool,
+    // Where the pointer was on the screen on the last update.
+    pointer_on_screen: Vec2,
+    // The pointers held down on an element, by pointer id, along with the element id.
+    presses: Vec<(u64, usize)>,
+    // The last content_rect, along with the collapsed flag and element count it was laid out with.
+    content: Cell<Option<(bool, usize, Rect)>>,
+    // Built from the element rectangles of the cached layout, dropped along with it.
+    index: RefCell<Option<SpatialIndex>>,
+}
+impl Menu {
+    ///Create a new menu with the default arguments.
+    pub fn new(name: String, position: Vec2) -> Menu {
+        Menu{
+            title: Title {
+                name: name,
+                color: WHITE,
+                font_size: 25.0,
+                key: None,
+            },
+            visible: true,
+            color: DARKGRAY,
+            edge: 20.0,
+            position: position,
+            size: None,
+            elements: vec![],
+            opacity: 1.0,
+            animator: Animator::new(),
+            scale: 1.0,
+            collapsed: false,
+            collapsible: false,
+            scrollable: false,
+            scroll: Vec2::ZERO,
+
+            visible_color: DARKGRAY,
+            next_id: 0,
+            offset: Vec2::ZERO,
+            opacity_tween: None,
+            offset_tween: None,
+            hiding: false,
+            input: PointerTracker::new(),
+            events: vec![],
+            hovered: None,
+            overlay: None,
+            hover_time: 0.0,
+            tooltip_dismissed: false,
+            pointer_on_screen: Vec2::ZERO,
+            presses: Vec::new(),
+            content: Cell::new(None),
+            index: RefCell::new(None),
+        }
+    }
+    ///Adds a new menu element to the **menu.elements** vector,
+    ///and return a Element object that can be edited later.
+    pub fn add_element<T: MenuElement + 'static>(&mut self, element: T) ->  Element<T> {
+        self.insert_element(None, false, element)
+    }
+
+    ///Same as **add_element**, but the element can later be looked up with **find**.
+    pub fn add_named_element<T: MenuElement + 'static>(&mut self, name: &str, element: T) ->  Element<T> {
+        self.insert_element(Some(name.to_string()), false, element)
+    }
+
+    ///Adds a element that stays in the menu even after every returned **Element** is dropped.
+    pub fn add_owned_element<T: MenuElement + 'static>(&mut self, name: Option<&str>, element: T) ->  Element<T> {
+        self.insert_element(name.map(str::to_string), true, element)
+    }
+
+    fn insert_element<T: MenuElement + 'static>(&mut self, name: Option<String>, owned: bool, element: T) ->  Element<T> {
+        let element = Element{
+            id: self.next_id,
+            name,
+            owned,
+            data: Rc::new(RefCell::new(element)),
+            dirty: Rc::new(Cell::new(true)),
+            rect: Cell::new(None),
+            type_name: std::any::type_name::<T>(),
+            tooltip: Rc::new(RefCell::new(None)),
+        };
+        self.next_id += 1;
+        self.elements.push(Element{
+            id: element.id,
+            name: element.name.clone(),
+            owned,
+            data: element.data.clone(),
+            dirty: element.dirty.clone(),
+            rect: Cell::new(None),
+            type_name: element.type_name,
+            tooltip: element.tooltip.clone(),
+        });
+
+        element
+    }
+
+    ///Return the element added with the given name.
+    pub fn find(&self, name: &str) -> Option<Element<dyn MenuElement>> {
+        self.elements.iter()
+            .find(|element| element.name() == Some(name))
+            .cloned()
+    }
+
+    ///Tween a property of an element, e.g. `|button| &mut button.color`, from its
+    ///current value to `to` over `duration` seconds.
+    pub fn animate<T: MenuElement + 'static, V: Lerp + 'static>(&mut self, element: &Element<T>, property: fn(&mut T) -> &mut V, to: V, duration: f32, easing: Easing) {
+        let from = *property(&mut element.edit());
+        self.animator.add(Rc::downgrade(&element.data), property, Tween::new(from, to, duration, easing));
+    }
+
+    ///Make the menu visible, playing the given transition.
+    pub fn show(&mut self, transition: Transition) {
+        let was_visible = self.visible && !self.hiding;
+        self.visible = true;
+        self.hiding = false;
+        self.opacity_tween = None;
+        self.offset_tween = None;
+
+        match transition {
+            Transition::Instant => {}
+            Transition::Fade { duration, easing } => {
+                let from = if was_visible { self.opacity } else { 0.0 };
+                self.opacity_tween = Some(Tween::new(from, 1.0, duration, easing));
+            }
+            Transition::Slide { offset, duration, easing } => {
+                let from = if was_visible { self.offset } else { offset };
+                self.offset_tween = Some(Tween::new(from, Vec2::ZERO, duration, easing));
+            }
+            Transition::SlideFade { offset, duration, easing } => {
+                let (from_opacity, from_offset) = if was_visible { (self.opacity, self.offset) } else { (0.0, offset) };
+                self.opacity_tween = Some(Tween::new(from_opacity, 1.0, duration, easing));
+                self.offset_tween = Some(Tween::new(from_offset, Vec2::ZERO, duration, easing));
+            }
+        }
+        if self.opacity_tween.is_none() {
+            self.opacity = 1.0;
+        }
+        if self.offset_tween.is_none() {
+            self.offset = Vec2::ZERO;
+        }
+    }
+
+    ///Hide the menu, playing the given transition before **visible** is set to false.
+    pub fn hide(&mut self, transition: Transition) {
+        if !self.visible {
+            return;
+        }
+        self.hiding = true;
+        self.opacity_tween = None;
+        self.offset_tween = None;
+
+        match transition {
+            Transition::Instant => {}
+            Transition::Fade { duration, easing } => {
+                self.opacity_tween = Some(Tween::new(self.opacity, 0.0, duration, easing));
+            }
+            Transition::Slide { offset, duration, easing } => {
+                self.offset_tween = Some(Tween::new(self.offset, offset, duration, easing));
+            }
+            Transition::SlideFade { offset, duration, easing } => {
+                self.opacity_tween = Some(Tween::new(self.opacity, 0.0, duration, easing));
+                self.offset_tween = Some(Tween::new(self.offset, offset, duration, easing));
+            }
+        }
+        self.update_transition(0.0);
+    }
+
+    ///Return true while a show or hide transition is playing.
+    pub fn is_transitioning(&self) -> bool {
+        self.opacity_tween.is_some() || self.offset_tween.is_some()
+    }
+
+    fn update_transition(&mut self, delta: f32) {
+        if let Some(tween) = &mut self.opacity_tween {
+            self.opacity = tween.step(delta);
+            if tween.is_finished() {
+                self.opacity_tween = None;
+            }
+        }
+        if let Some(tween) = &mut self.offset_tween {
+            self.offset = tween.step(delta);
+            if tween.is_finished() {
+                self.offset_tween = None;
+            }
+        }
+
+        if self.hiding && !self.is_transitioning() {
+            self.hiding = false;
+            self.visible = false;
+            self.opacity = 1.0;
+            self.offset = Vec2::ZERO;
+        }
+    }
+
+    ///Return the rectangle holding every element, relative to where they are laid out from.
+    ///
+    ///Cached until an element is edited, changes its bounding rectangle while updating, or is
+    ///added or removed.
+    fn content_rect(&self) -> Rect {
+        if let Some((collapsed, count, rect)) = self.content.get() {
+            let is_dirty = self.elements.iter().any(|element| element.dirty.get());
+            if collapsed == self.collapsed && count == self.elements.len() && !is_dirty {
+                return rect;
+            }
+        }
+
+        // Calculate the bounding rectangle for the menu.
+        let mut elements_rect = Rect::new(0.0, 0.0, 0.0, 0.0);
+        self.index.replace(None);
+
+        // Iterate over each element in the menu, a collapsed menu only has its title.
+        for element in &self.elements {
+            // Get the bounding rectangle for the element.
+            let rect = element.read().bounding_rect();
+            element.rect.set(rect);
+            element.dirty.set(false);
+
+            if let (Some(rect), false) = (rect, self.collapsed) {
+                // Combine the element rectangle with the overall menu rectangle.
+                elements_rect = elements_rect.combine_with(rect);
+            }
+        }
+
+        self.content.set(Some((self.collapsed, self.elements.len(), elements_rect)));
+        elements_rect
+    }
+
+    ///Return the area elements are shown in when the menu scrolls, given where they are laid out from.
+    fn scroll_view(&self, content_origin: Vec2) -> Option<Rect> {
+        let size = self.size.filter(|_| self.scrollable)?;
+        Some(Rect::new(content_origin.x, content_origin.y, size.x, size.y))
+    }
+
+    fn calculate_menu_rect(&self) -> (Rect, Rect) {
+        let mut elements_rect = self.content_rect();
+
+        // Get the size of the menu title text.
+        let text_size = measure_text(&self.title.text(), self.title.font_size as u16);
+
+        // Create a rectangle for the menu title.
+        let title_rect = Rect {
+            x: elements_rect.x + elements_rect.w / 2.0,
+            y: elements_rect.y,
+            w: text_size.width,
+            h: text_size.height,
+        };
+
+        // Combine the menu title rectangle with the overall menu rectangle.
+        elements_rect = elements_rect.combine_with(title_rect);
 
         // If the menu has a specified size, use it to set the menu rectangle.
         if let Some(size) = self.size {
@@ -552,8 +1527,8 @@ impl Menu {
 
         // Offset the menu rectangle by the menu position and return it.
         let menu_rect = Rect {
-            x: elements_rect.x + self.position.x,
-            y: elements_rect.y + self.position.y,
+            x: elements_rect.x + self.position.x + self.offset.x,
+            y: elements_rect.y + self.position.y + self.offset.y,
             w: elements_rect.w,
             h: elements_rect.h,
         };
@@ -561,102 +1536,439 @@ impl Menu {
         (menu_rect, title_rect)
     }
 
+    ///Return the rectangles of the menu and of its title on the screen, for a menu whose
+    ///parent lays its elements out from `origin`.
+    fn screen_rects(&self, origin: Vec2) -> (Rect, Rect) {
+        let (menu_rect, title_rect) = self.calculate_menu_rect();
+        (menu_rect.offset(origin), title_rect.offset(origin))
+    }
+
+    ///Return the rectangle of the menu background, edges and title included.
+    fn background_rect(&self, menu_rect: Rect, title_rect: Rect) -> Rect {
+        Rect{
+            x: menu_rect.x - self.edge,
+            y: menu_rect.y - self.edge,
+            w: menu_rect.w + (self.edge * 2.0),
+            h: menu_rect.h + (self.edge * 2.0) + title_rect.h,
+        }
+    }
+
     pub fn update(&mut self) {
+        let delta = self.animator.tick();
+        let frame = self.input.poll(delta);
+        let ((), frame) = input::with_frame(frame, || self.update_at(Vec2::ZERO, delta));
+        self.input.keep_captures(&frame.pointers);
+    }
+
+    ///Read the mouse and touches from `source` instead of macroquad.
+    pub fn set_input<S: InputSource + 'static>(&mut self, source: S) {
+        self.input.set_source(source);
+    }
+
+    ///Record the input of every update from now on, see **stop_recording**.
+    pub fn start_recording(&mut self) {
+        self.input.start_recording();
+    }
+
+    ///Return the input recorded since **start_recording**, or `None` when not recording.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.input.stop_recording()
+    }
+
+    ///Update the menu with the frames of `recording` from now on, one per update, and with
+    ///their time steps instead of its clock.
+    pub fn replay(&mut self, recording: InputRecording) -> Replay {
+        let replay = Replay::new(recording);
+        self.set_input(replay.clone());
+        let clock = replay.clone();
+        self.animator.set_clock(move || clock.delta());
+
+        replay
+    }
+
+    ///Return the area the menu covers on the screen, or `None` while it is hidden.
+    ///
+    ///For a nested menu the area is relative to where its parent lays its elements out.
+    pub fn screen_rect(&self) -> Option<Rect> {
+        if !self.visible {
+            return None;
+        }
+        Some(with_scale(self.scale, self.position, || {
+            let (menu_rect, title_rect) = self.screen_rects(Vec2::ZERO);
+            rect_to_screen(self.background_rect(menu_rect, title_rect))
+        }))
+    }
+
+    ///Return the area `element` covers on the screen, or `None` when it isn't in this menu or isn't shown.
+    pub fn element_rect<T: MenuElement + ?Sized>(&self, element: &Element<T>) -> Option<Rect> {
+        let is_in_menu = self.elements.iter()
+            .any(|other| Rc::as_ptr(&other.data) as *const () == Rc::as_ptr(&element.data) as *const ());
+        if !self.visible || self.collapsed || !is_in_menu {
+            return None;
+        }
+        let rect = element.read().bounding_rect()?;
+
+        Some(with_scale(self.scale, self.position, || {
+            let (menu_rect, title_rect) = self.screen_rects(Vec2::ZERO);
+            let mut content_origin = vec2(menu_rect.x, menu_rect.y + title_rect.h);
+            if self.scroll_view(content_origin).is_some() {
+                content_origin -= self.scroll;
+            }
+            rect_to_screen(rect.offset(content_origin))
+        }))
+    }
+
+    ///Return the events that happened to the elements since the last call, like long-presses,
+    ///right-clicks and double-clicks.
+    pub fn take_events(&mut self) -> Vec<MenuEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Update the menu as a child of a menu laying its elements out from `origin`, or as a root with `Vec2::ZERO`.
+    fn update_at(&mut self, origin: Vec2, delta: f32) {
         self.visible_color = self.color;
+        let was_hovered = self.hovered.take();
+        let pointer = pointer_position();
+        self.pointer_on_screen = rect_to_screen(Rect::new(pointer.x, pointer.y, 0.0, 0.0)).point();
+
+        self.update_transition(delta);
 
         //Remove elements out of scope
-        let mut index_to_delete: Vec<usize> = vec![];
-        for element_ref in &self.elements {
-            if Rc::strong_count(&element_ref.data) <= 1 {
-                index_to_delete.push(element_ref.id);
+        self.elements.retain(|element_ref| element_ref.owned || Rc::strong_count(&element_ref.data) > 1);
+
+        // A hidden menu, or one fading out, takes no input.
+        if !self.visible || self.hiding {
+            self.hover_time = 0.0;
+            return;
+        }
+
+        // Everything below is scaled around the menu position.
+        with_scale(self.scale, origin + self.position, || {
+            let (menu_rect, menu_tile_rect) = self.screen_rects(origin);
+            if self.collapsible && pointer_pressed() {
+                let title_bar = Rect{
+                    x: menu_rect.x - self.edge,
+                    y: menu_rect.y - self.edge,
+                    w: menu_rect.w + (self.edge * 2.0),
+                    h: self.edge + menu_tile_rect.h,
+                };
+                if title_bar.contains(pointer_position()) {
+                    self.collapsed = !self.collapsed;
+                }
+            }
+            if self.collapsed {
+                return;
+            }
+
+            let content_origin = vec2(menu_rect.x, menu_rect.y + menu_tile_rect.h);
+            match self.scroll_view(content_origin) {
+                Some(view) => {
+                    self.scroll_by_drag(view);
+                    with_clip(view, || self.update_elements(content_origin - self.scroll));
+                }
+                None => self.update_elements(content_origin),
+            }
+        });
+
+        if self.hovered.is_some() && self.hovered == was_hovered {
+            self.hover_time += delta;
+        } else {
+            self.hover_time = 0.0;
+            self.tooltip_dismissed = false;
+        }
+        self.tooltip_dismissed |= pointer_pressed();
+    }
+
+    ///Return the position in **elements** of the element on top at `point`, relative to where they are laid out from.
+    fn element_under(&self, point: Vec2) -> Option<usize> {
+        if let Some((id, _)) = self.overlay.filter(|(_, rect)| rect.contains(point)) {
+            return self.elements.iter().position(|element| element.id == id);
+        }
+        let mut index = self.index.borrow_mut();
+        index.get_or_insert_with(|| SpatialIndex::new(self.elements.iter().map(|element| element.rect.get()).collect()))
+            .find(point)
+    }
+
+    fn update_elements(&mut self, content_origin: Vec2) {
+        // Hand each pointer only to the element on top under it, or to the element it went down on
+        // until it goes up, so that element sees it released even off itself.
+        let targeted = targeted_pointers();
+        let mut routed: Vec<(usize, u64)> = Vec::new();
+        for pointer in &targeted {
+            let pressed_on = self.presses.iter().find(|(id, _)| *id == pointer.id).map(|(_, element)| *element);
+            let target = match pressed_on {
+                Some(element) if !pointer.pressed => self.elements.iter().position(|element_ref| element_ref.id == element),
+                _ => self.element_under(pointer.position - content_origin),
+            };
+            if pointer.pressed {
+                self.presses.retain(|(id, _)| *id != pointer.id);
+                if let Some(target) = target {
+                    self.presses.push((pointer.id, self.elements[target].id));
+                }
             }
+            if let Some(target) = target {
+                routed.push((target, pointer.id));
+            }
+        }
+        self.presses.retain(|(id, _)| targeted.iter().any(|pointer| pointer.id == *id && pointer.down));
+        self.hovered = self.element_under(pointer_position() - content_origin).map(|position| self.elements[position].id);
+
+        let mut has_moved = false;
+        let mut overlay = None;
+        for (position, element_ref) in self.elements.iter().enumerate() {
+            // Borrowed directly, updating an element doesn't make it dirty unless its rectangle changes.
+            let mut element = element_ref.data.deref().borrow_mut();
+
+            let targets = routed.iter().filter(|(target, _)| *target == position).map(|(_, id)| *id).collect();
+            with_targets(targets, || element.update(content_origin));
+            let rect = element.bounding_rect();
+            if rect != element_ref.rect.get() {
+                element_ref.rect.set(rect);
+                has_moved = true;
+            }
+            overlay = element.overlay_rect().map(|rect| (element_ref.id, rect)).or(overlay);
+        }
+        self.overlay = overlay;
+        if has_moved {
+            self.content.set(None);
+            self.index.replace(None);
+        }
+
+        for pointer in pointers() {
+            let kinds = [
+                (pointer.long_pressed, EventKind::LongPress),
+                (pointer.right_clicked, EventKind::RightClick),
+                (pointer.middle_clicked, EventKind::MiddleClick),
+                (pointer.double_clicked, EventKind::DoubleClick),
+            ];
+            let element = self.element_under(pointer.position - content_origin).map(|position| &self.elements[position]);
+            // Disabled elements ignore input.
+            let Some(element) = element.filter(|element| element.read().is_enabled()) else {
+                continue;
+            };
+            for (_, kind) in kinds.into_iter().filter(|(happened, _)| *happened) {
+                self.events.push(MenuEvent{
+                    kind,
+                    element: element.id(),
+                    name: element.name().map(str::to_string),
+                    position: pointer.position,
+                });
+            }
+        }
+    }
+
+    ///Scroll with the fingers dragging from inside `view`, unless an element captured them.
+    fn scroll_by_drag(&mut self, view: Rect) {
+        for pointer in pointers() {
+            if pointer.kind == PointerKind::Touch && pointer.dragging && !pointer.captured && view.contains(pointer.start) {
+                self.scroll -= pointer.delta;
+            }
+        }
+
+        let content = self.content_rect();
+        let max_scroll = Vec2{
+            x: (content.right() - view.w).max(0.0),
+            y: (content.bottom() - view.h).max(0.0),
+        };
+        self.scroll = self.scroll.clamp(Vec2::ZERO, max_scroll);
+    }
+
+    ///Return the state of the menu and of every named element in it, see **save_state**.
+    pub fn state(&self) -> MenuState {
+        let mut state = MenuState::new();
+        self.write_state("", &mut state);
+        state
+    }
+
+    ///Restore the state returned by **state**. Entries of elements that are no longer
+    ///in the menu are ignored, and elements missing from the state are left as they are.
+    pub fn restore_state(&mut self, state: &MenuState) {
+        self.read_state("", state);
+    }
+
+    ///Save the menu position, size and collapsed flag along with the state of every named
+    ///element, like check boxes, sliders and text inputs, to a file.
+    pub fn save_state<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        self.state().save(path)
+    }
+
+    ///Restore the state saved by **save_state**.
+    pub fn load_state<P: AsRef<std::path::Path>>(&mut self, path: P) -> std::io::Result<()> {
+        let state = MenuState::load(path)?;
+        self.restore_state(&state);
+        Ok(())
+    }
+
+    fn write_state(&self, path: &str, state: &mut MenuState) {
+        state.set_vec2(&property_key(path, "position"), self.position);
+        match self.size {
+            Some(size) => state.set_vec2(&property_key(path, "size"), size),
+            None => state.remove(&property_key(path, "size")),
         }
-        for (index, to_delet) in index_to_delete.iter().enumerate() {
-            let delet = self.elements.remove(*to_delet - index);
+        state.set_bool(&property_key(path, "collapsed"), self.collapsed);
+
+        for element in &self.elements {
+            if let Some(name) = element.name() {
+                element.read().save_state(&child_key(path, name), state);
+            }
         }
+    }
 
-        let (menu_rect, menu_tile_rect) = self.calculate_menu_rect();
-        for element_ref in self.elements.iter() {
-            let mut element = element_ref.edit();
+    fn read_state(&mut self, path: &str, state: &MenuState) {
+        if let Some(position) = state.get_vec2(&property_key(path, "position")) {
+            self.position = position;
+        }
+        if let Some(size) = state.get_vec2(&property_key(path, "size")) {
+            self.size = Some(size);
+        }
+        if let Some(collapsed) = state.get_bool(&property_key(path, "collapsed")) {
+            self.collapsed = collapsed;
+        }
 
-            element.update(vec2(menu_rect.x, menu_rect.y + menu_tile_rect.h))
+        for element in &self.elements {
+            if let Some(name) = element.name() {
+                element.edit().load_state(&child_key(path, name), state);
+            }
         }
     }
 
     ///Draw the menu
     pub fn draw(&self) {
+        // Drawn over the pointer, the menu hides the tooltips of the menus below it.
+        if self.screen_rect().is_some_and(|rect| rect.contains(self.pointer_on_screen)) {
+            tooltip::clear();
+        }
+        self.draw_at(Vec2::ZERO);
+        draw_deferred();
+    }
+
+    // Draw the menu as a child of a menu laying its elements out from `origin`, or as a root with `Vec2::ZERO`.
+    fn draw_at(&self, origin: Vec2) {
         if !self.visible {
             return;
         }
-        let (menu_rect, menu_title_rect) = self.calculate_menu_rect();
+        with_scale(self.scale, origin + self.position, || with_opacity(self.opacity, || {
+            let (menu_rect, menu_title_rect) = self.screen_rects(origin);
+            let menu_bg_rect = self.background_rect(menu_rect, menu_title_rect);
 
-        let menu_bg_rect = Rect{
-            x: menu_rect.x - self.edge,
-            y: menu_rect.y - self.edge,
-            w: menu_rect.w + (self.edge * 2.0),
-            h: menu_rect.h + (self.edge * 2.0) + menu_title_rect.h,
-        };
+            // draw the menu background
+            draw_rectangle(menu_bg_rect.x, menu_bg_rect.y, menu_bg_rect.w, menu_bg_rect.h, self.visible_color);
 
-        // draw the menu background
-        draw_rectangle(menu_bg_rect.x, menu_bg_rect.y, menu_bg_rect.w, menu_bg_rect.h, self.visible_color);
+            // draw the menu name
+            let name_position = vec2(menu_rect.x + (menu_rect.w - menu_title_rect.w) / 2.0, menu_rect.y );
+            draw_text(&self.title.text(), name_position.x, name_position.y, self.title.font_size, self.title.color);
 
-        // draw the menu name
-        let name_position = vec2(menu_rect.x + (menu_rect.w - menu_title_rect.w) / 2.0, menu_rect.y );
-        draw_text(&self.title.name, name_position.x, name_position.y, self.title.font_size, self.title.color);
+            // draw the menu elements
+            if self.collapsed {
+                return;
+            }
+            let content_origin = Vec2{ x: menu_rect.x, y: menu_rect.y + menu_title_rect.h};
+            match self.scroll_view(content_origin) {
+                Some(view) => with_clip(view, || self.draw_elements(content_origin - self.scroll)),
+                None => self.draw_elements(content_origin),
+            }
+        }));
+        if debug::overlay() {
+            with_scale(self.scale, origin + self.position, || self.draw_debug_overlay(origin));
+        }
+    }
 
-        // draw the menu elements
+    fn draw_elements(&self, content_origin: Vec2) {
+        // Queued before drawing the elements, so the tooltip of an element in a nested menu wins.
+        let hovered = self.elements.iter().find(|element| Some(element.id) == self.hovered);
+        if let Some(element) = hovered.filter(|_| self.hover_time >= tooltip::delay() && !self.tooltip_dismissed) {
+            let shown = match element.read().tooltip() {
+                Some(text) => Some(Rc::new(RefCell::new(Some(Tooltip::Text(text))))),
+                None => element.tooltip.deref().borrow().is_some().then(|| element.tooltip.clone()),
+            };
+            if let Some(shown) = shown {
+                tooltip::queue(self.pointer_on_screen, shown);
+            }
+        }
         for element in &self.elements {
-            element.read().draw(Vec2{ x: menu_rect.x, y: menu_rect.y + menu_title_rect.h});
+            element.read().draw(content_origin);
         }
+        if debug::overlay() {
+            for element in &self.elements {
+                if let Some(rect) = element.read().bounding_rect() {
+                    let type_name = debug::short_type_name(element.type_name());
+                    debug::draw_element_outline(rect.offset(content_origin), element.id, &type_name, self.hovered == Some(element.id));
+                }
+            }
+        }
+    }
+
+    ///Outline the background with its edge padding, and the menu and title rectangles of the layout.
+    fn draw_debug_overlay(&self, origin: Vec2) {
+        let (menu_rect, title_rect) = self.screen_rects(origin);
+        debug::draw_labeled_outline(self.background_rect(menu_rect, title_rect), &format!("edge {}", self.edge), debug::EDGE_COLOR);
+        debug::draw_labeled_outline(menu_rect, "menu", debug::MENU_COLOR);
+        // The layout places the title relative to the elements, before the menu position is added.
+        let title_rect = title_rect.offset(self.position + self.offset);
+        debug::draw_labeled_outline(title_rect, "title", debug::TITLE_COLOR);
     }
 }
 impl MenuElement for Menu {
     fn update(&mut self, menu_position: Vec2) {
-        self.update();
+        // A nested menu runs on the clock of its root menu.
+        let delta = input::frame_time();
+        self.animator.advance(delta);
+        self.update_at(menu_position, delta);
     }
 
     fn draw(&self, menu_position: Vec2) {
-        if !self.visible {
-            return;
-        }
-        let (mut menu_rect, mut menu_title_rect) = self.calculate_menu_rect();
-        menu_rect = Rect{
-            x: menu_rect.x + menu_position.x,
-            y: menu_rect.y + menu_position.y,
-            w: menu_rect.w,
-            h: menu_rect.h,
-        };
-        menu_title_rect = Rect{
-            x: menu_title_rect.x + menu_position.x,
-            y: menu_title_rect.y + menu_position.y,
-            w: menu_title_rect.w,
-            h: menu_title_rect.h,
-        };
+        self.draw_at(menu_position);
+    }
 
-        let menu_bg_rect = Rect{
-            x: menu_rect.x - self.edge,
-            y: menu_rect.y - self.edge,
-            w: menu_rect.w + (self.edge * 2.0),
-            h: menu_rect.h + (self.edge * 2.0) + menu_title_rect.h,
-        };
+    fn save_state(&self, key: &str, state: &mut MenuState) {
+        self.write_state(key, state);
+    }
 
-        // draw the menu background
-        draw_rectangle(menu_bg_rect.x, menu_bg_rect.y, menu_bg_rect.w, menu_bg_rect.h, self.visible_color);
+    fn load_state(&mut self, key: &str, state: &MenuState) {
+        self.read_state(key, state);
+    }
 
-        // draw the menu name
-        let name_position = vec2(menu_rect.x + (menu_rect.w - menu_title_rect.w) / 2.0, menu_rect.y );
-        draw_text(&self.title.name, name_position.x, name_position.y, self.title.font_size, self.title.color);
+    fn properties(&self) -> Properties {
+        Properties{
+            position: Some(self.position),
+            // A menu without a size shows the one it fits its elements with.
+            size: Some(self.calculate_menu_rect().0.size()),
+            color: Some(self.color),
+            title: Some(self.title.name.clone()),
+            visible: Some(self.visible),
+            enabled: None,
+        }
+    }
 
-        // draw the menu elements
-        for element in &self.elements {
-            element.read().draw(Vec2{ x: menu_rect.x, y: menu_rect.y + menu_title_rect.h});
+    fn set_properties(&mut self, properties: &Properties) {
+        set_if(&mut self.position, &properties.position);
+        // Only a size edited by hand stops the menu from fitting its elements.
+        if let Some(size) = properties.size.filter(|size| *size != self.calculate_menu_rect().0.size()) {
+            self.size = Some(size);
         }
+        set_if(&mut self.color, &properties.color);
+        set_if(&mut self.title.name, &properties.title);
+        set_if(&mut self.visible, &properties.visible);
+    }
+
+    fn children(&self) -> &[Element<dyn MenuElement>] {
+        &self.elements
     }
 
+    ///Return the area of the drawn background, relative to the parent menu like any other element.
     fn bounding_rect(&self) -> Option<Rect> {
         if !self.visible {return None}
-        let (menu_rect, text_rect) = self.calculate_menu_rect();
-
-        Some(menu_rect)
+        let rect = with_scale(self.scale, self.position, || {
+            let (menu_rect, title_rect) = self.calculate_menu_rect();
+            self.background_rect(menu_rect, title_rect)
+        });
+
+        // Scaled around the menu position, the way it is drawn.
+        Some(Rect{
+            x: self.position.x + (rect.x - self.position.x) * self.scale,
+            y: self.position.y + (rect.y - self.position.y) * self.scale,
+            w: rect.w * self.scale,
+            h: rect.h * self.scale,
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,376 @@
+//! Build a `Menu` from a RON or JSON description.
+//!
+//! ```ron
+//! Menu(
+//!     title: "Menu 1",
+//!     position: (50.0, 50.0),
+//!     elements: [
+//!         Button(name: "start", label: "Start", key: Some("menu.start"), position: (0.0, 120.0)),
+//!         TextLabel(name: "fps", label: "FPS: ", position: (30.0, 50.0)),
+//!         CheckBox(name: "vsync", position: (20.0, 90.0), size: (20.0, 20.0), position_type: Center),
+//!     ],
+//! )
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use macroquad::prelude::*;
+use serde::Deserialize;
+use crate::{Button, CheckBox, Element, Menu, MenuElement, PositionType, TextLabel};
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename = "PositionType")]
+enum PositionTypeDef {
+    #[default]
+    TopLeft,
+    Center,
+}
+impl From<PositionTypeDef> for PositionType {
+    fn from(position_type: PositionTypeDef) -> Self {
+        match position_type {
+            PositionTypeDef::TopLeft => PositionType::TopLeft,
+            PositionTypeDef::Center => PositionType::Center,
+        }
+    }
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Element", deny_unknown_fields)]
+enum ElementDef {
+    Menu {
+        #[serde(default)]
+        name: Option<String>,
+        title: String,
+        key: Option<String>,
+        position: (f32, f32),
+        size: Option<(f32, f32)>,
+        color: Option<[f32; 4]>,
+        edge: Option<f32>,
+        scale: Option<f32>,
+        #[serde(default)]
+        scrollable: bool,
+        #[serde(default = "default_visible")]
+        visible: bool,
+        #[serde(default)]
+        elements: Vec<ElementDef>,
+    },
+    Button {
+        #[serde(default)]
+        name: Option<String>,
+        label: String,
+        key: Option<String>,
+        position: (f32, f32),
+        #[serde(default)]
+        position_type: PositionTypeDef,
+        size: Option<(f32, f32)>,
+        color: Option<[f32; 4]>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        disabled_reason: Option<String>,
+    },
+    CheckBox {
+        #[serde(default)]
+        name: Option<String>,
+        position: (f32, f32),
+        size: (f32, f32),
+        #[serde(default)]
+        position_type: PositionTypeDef,
+        color: Option<[f32; 4]>,
+        #[serde(default)]
+        checked: bool,
+        #[serde(default = "default_visible")]
+        visible: bool,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        disabled_reason: Option<String>,
+    },
+    TextLabel {
+        #[serde(default)]
+        name: Option<String>,
+        label: String,
+        key: Option<String>,
+        position: (f32, f32),
+        #[serde(default)]
+        position_type: PositionTypeDef,
+        color: Option<[f32; 4]>,
+        #[serde(default = "default_visible")]
+        visible: bool,
+    },
+}
+
+///A typed handle to an element created by the loader.
+pub enum LoadedElement {
+    Menu(Element<Menu>),
+    Button(Element<Button>),
+    CheckBox(Element<CheckBox>),
+    TextLabel(Element<TextLabel>),
+}
+impl LoadedElement {
+    pub fn as_menu(&self) -> Option<Element<Menu>> {
+        match self {
+            LoadedElement::Menu(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+    pub fn as_button(&self) -> Option<Element<Button>> {
+        match self {
+            LoadedElement::Button(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+    pub fn as_check_box(&self) -> Option<Element<CheckBox>> {
+        match self {
+            LoadedElement::CheckBox(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+    pub fn as_text_label(&self) -> Option<Element<TextLabel>> {
+        match self {
+            LoadedElement::TextLabel(element) => Some(element.clone()),
+            _ => None,
+        }
+    }
+}
+
+///The menu built by the loader, along with the handles of every named element.
+pub struct Layout {
+    pub menu: Menu,
+    pub elements: HashMap<String, LoadedElement>,
+}
+impl Layout {
+    pub fn menu(&self, name: &str) -> Option<Element<Menu>> {
+        self.elements.get(name).and_then(LoadedElement::as_menu)
+    }
+    pub fn button(&self, name: &str) -> Option<Element<Button>> {
+        self.elements.get(name).and_then(LoadedElement::as_button)
+    }
+    pub fn check_box(&self, name: &str) -> Option<Element<CheckBox>> {
+        self.elements.get(name).and_then(LoadedElement::as_check_box)
+    }
+    pub fn text_label(&self, name: &str) -> Option<Element<TextLabel>> {
+        self.elements.get(name).and_then(LoadedElement::as_text_label)
+    }
+}
+
+///Error returned when a menu description can't be loaded.
+#[derive(Debug)]
+pub struct LoadError {
+    ///Line and column of the error in the source, when known.
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+impl std::error::Error for LoadError {}
+
+impl LoadError {
+    fn new(message: String) -> LoadError {
+        LoadError{
+            position: None,
+            message,
+        }
+    }
+}
+
+///Build a menu from a RON description. Optional fields can be written without `Some(..)`.
+#[cfg(feature = "ron")]
+pub fn load_ron(source: &str) -> Result<Layout, LoadError> {
+    let options = ron::Options::default().with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME);
+    let definition: ElementDef = options.from_str(source).map_err(|error| LoadError{
+        position: Some((error.position.line, error.position.col)),
+        message: error.code.to_string(),
+    })?;
+    build_layout(definition, source)
+}
+
+///Build a menu from a JSON description.
+#[cfg(feature = "json")]
+pub fn load_json(source: &str) -> Result<Layout, LoadError> {
+    let definition: ElementDef = serde_json::from_str(source).map_err(|error| {
+        // serde_json appends the position to its message, it is already kept apart here.
+        let message = error.to_string();
+        let message = match message.rsplit_once(" at line ") {
+            Some((message, _)) => message.to_string(),
+            None => message,
+        };
+        LoadError{
+            position: Some((error.line(), error.column())),
+            message,
+        }
+    })?;
+    build_layout(definition, source)
+}
+
+///Build a menu from a `.ron` or `.json` file, picking the format from the extension.
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Layout, LoadError> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path)
+        .map_err(|error| LoadError::new(format!("{}: {}", path.display(), error)))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        #[cfg(feature = "ron")]
+        Some("ron") => load_ron(&source),
+        #[cfg(feature = "json")]
+        Some("json") => load_json(&source),
+        _ => Err(LoadError::new(format!("{}: unsupported menu file format", path.display()))),
+    }
+}
+
+fn build_layout(definition: ElementDef, source: &str) -> Result<Layout, LoadError> {
+    let mut elements = HashMap::new();
+    match definition {
+        ElementDef::Menu { name: None, .. } => {}
+        ElementDef::Menu { name: Some(name), .. } => {
+            return Err(LoadError::new(format!("the root menu can't be named, found name \"{}\"", name)));
+        }
+        ElementDef::Button { .. } => return Err(LoadError::new("the root element must be a Menu, found a Button".to_string())),
+        ElementDef::CheckBox { .. } => return Err(LoadError::new("the root element must be a Menu, found a CheckBox".to_string())),
+        ElementDef::TextLabel { .. } => return Err(LoadError::new("the root element must be a Menu, found a TextLabel".to_string())),
+    }
+
+    let menu = build_menu(definition, source, &mut elements)?;
+    Ok(Layout{
+        menu,
+        elements,
+    })
+}
+
+fn to_color(color: [f32; 4]) -> Color {
+    Color::new(color[0], color[1], color[2], color[3])
+}
+
+fn to_vec2(vector: (f32, f32)) -> Vec2 {
+    vec2(vector.0, vector.1)
+}
+
+fn build_menu(definition: ElementDef, source: &str, names: &mut HashMap<String, LoadedElement>) -> Result<Menu, LoadError> {
+    let ElementDef::Menu { title, key, position, size, color, edge, scale, scrollable, visible, elements, .. } = definition else {
+        unreachable!("build_menu is only called with menu definitions")
+    };
+
+    let mut menu = Menu::new(title, to_vec2(position));
+    menu.title.key = key;
+    menu.size = size.map(to_vec2);
+    menu.visible = visible;
+    menu.scrollable = scrollable;
+    if let Some(color) = color {
+        menu.color = to_color(color);
+    }
+    if let Some(edge) = edge {
+        menu.edge = edge;
+    }
+    if let Some(scale) = scale {
+        menu.scale = scale;
+    }
+
+    for element in elements {
+        add_definition(&mut menu, element, source, names)?;
+    }
+
+    Ok(menu)
+}
+
+fn add_definition(menu: &mut Menu, definition: ElementDef, source: &str, names: &mut HashMap<String, LoadedElement>) -> Result<(), LoadError> {
+    match definition {
+        ElementDef::Menu { ref name, .. } => {
+            let name = name.clone();
+            let child = build_menu(definition, source, names)?;
+            let element = add_to_menu(menu, &name, child);
+            register(names, source, name, LoadedElement::Menu(element))
+        }
+        ElementDef::Button { name, label, key, position, position_type, size, color, visible, enabled, disabled_reason } => {
+            let mut button = Button::new(label, position_type.into(), to_vec2(position), size.map(to_vec2));
+            button.title.key = key;
+            button.visible = visible;
+            button.enabled = enabled;
+            button.disabled_reason = disabled_reason;
+            if let Some(color) = color {
+                button.color = to_color(color);
+            }
+            let element = add_to_menu(menu, &name, button);
+            register(names, source, name, LoadedElement::Button(element))
+        }
+        ElementDef::CheckBox { name, position, size, position_type, color, checked, visible, enabled, disabled_reason } => {
+            let mut check_box = CheckBox::new(position_type.into(), to_vec2(position), to_vec2(size));
+            check_box.is_checked = checked;
+            check_box.visible = visible;
+            check_box.enabled = enabled;
+            check_box.disabled_reason = disabled_reason;
+            if let Some(color) = color {
+                check_box.color = to_color(color);
+            }
+            let element = add_to_menu(menu, &name, check_box);
+            register(names, source, name, LoadedElement::CheckBox(element))
+        }
+        ElementDef::TextLabel { name, label, key, position, position_type, color, visible } => {
+            let mut text_label = TextLabel::new(label, position_type.into(), to_vec2(position));
+            text_label.title.key = key;
+            text_label.visible = visible;
+            if let Some(color) = color {
+                text_label.title.color = to_color(color);
+            }
+            let element = add_to_menu(menu, &name, text_label);
+            register(names, source, name, LoadedElement::TextLabel(element))
+        }
+    }
+}
+
+fn add_to_menu<T: MenuElement + 'static>(menu: &mut Menu, name: &Option<String>, element: T) -> Element<T> {
+    // The menu owns what the loader builds, so unnamed elements aren't dropped on the first update.
+    menu.add_owned_element(name.as_deref(), element)
+}
+
+///Remember the element by its name, failing on a name already used, pointing at it in `source`.
+fn register(names: &mut HashMap<String, LoadedElement>, source: &str, name: Option<String>, element: LoadedElement) -> Result<(), LoadError> {
+    let Some(name) = name else {
+        return Ok(());
+    };
+    if names.contains_key(&name) {
+        return Err(LoadError{
+            position: name_position(source, &name),
+            message: format!("duplicate element name \"{}\"", name),
+        });
+    }
+    names.insert(name, element);
+
+    Ok(())
+}
+
+///Return the line and column where `name` is given to an element the second time in `source`.
+fn name_position(source: &str, name: &str) -> Option<(usize, usize)> {
+    let quoted = format!("\"{}\"", name);
+    let offset = source.match_indices(&quoted)
+        .map(|(offset, _)| offset)
+        .filter(|offset| is_name_field(&source[..*offset]))
+        .nth(1)?;
+
+    let line_start = source[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+    Some((source[..offset].matches('\n').count() + 1, source[line_start..offset].chars().count() + 1))
+}
+
+///Return whether `before` ends with a `name:` field, in RON or JSON.
+fn is_name_field(before: &str) -> bool {
+    let Some(before) = before.trim_end().strip_suffix(':') else {
+        return false;
+    };
+    let before = before.trim_end();
+    let before = before.strip_suffix('"').unwrap_or(before);
+    before.strip_suffix("name")
+        .is_some_and(|before| !before.ends_with(|character: char| character.is_alphanumeric() || character == '_'))
+}
@@ -0,0 +1,359 @@
+//! Where menus draw to and measure their text with.
+//!
+//! Elements lay themselves out in their own coordinates, this module maps them to the screen
+//! through the scale and clip of the menus they are in, and hands the result to the current
+//! `Renderer`: macroquad by default, or something else, like the mock renderer of `testing`.
+//! What is drawn can also be recorded as a `DisplayList` with **record**.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use macroquad::prelude::*;
+use macroquad::text::TextDimensions;
+
+///Draws the menus, in screen coordinates.
+pub trait Renderer {
+    fn draw_rectangle(&mut self, rect: Rect, color: Color);
+    fn draw_circle(&mut self, center: Vec2, radius: f32, color: Color);
+    ///Draw `text` with its baseline starting at `position`.
+    fn draw_text(&mut self, text: &str, position: Vec2, font_size: f32, color: Color);
+    ///Measure `text` drawn at `font_size` pixels.
+    fn measure_text(&mut self, text: &str, font_size: u16) -> TextDimensions;
+    ///Limit drawing to `clip`, or stop limiting it with `None`.
+    fn set_clip(&mut self, clip: Option<Rect>);
+    ///Return the size of the screen, which tooltips are kept on, or `None` when it isn't known.
+    fn screen_size(&mut self) -> Option<Vec2> {
+        None
+    }
+}
+
+///Draws with macroquad, on the current window.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MacroquadRenderer;
+impl Renderer for MacroquadRenderer {
+    fn draw_rectangle(&mut self, rect: Rect, color: Color) {
+        macroquad::prelude::draw_rectangle(rect.x, rect.y, rect.w, rect.h, color)
+    }
+
+    fn draw_circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        macroquad::prelude::draw_circle(center.x, center.y, radius, color)
+    }
+
+    fn draw_text(&mut self, text: &str, position: Vec2, font_size: f32, color: Color) {
+        macroquad::prelude::draw_text(text, position.x, position.y, font_size, color);
+    }
+
+    fn measure_text(&mut self, text: &str, font_size: u16) -> TextDimensions {
+        macroquad::prelude::measure_text(text, None, font_size, 1.0)
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) {
+        // The scissor is set in pixels while macroquad draws in points.
+        let gl = unsafe { get_internal_gl() };
+        let dpi_scale = gl.quad_context.dpi_scale();
+        gl.quad_gl.scissor(clip.map(|clip| (
+            (clip.x * dpi_scale) as i32,
+            (clip.y * dpi_scale) as i32,
+            (clip.w * dpi_scale) as i32,
+            (clip.h * dpi_scale) as i32,
+        )));
+    }
+
+    fn screen_size(&mut self) -> Option<Vec2> {
+        Some(vec2(screen_width(), screen_height()))
+    }
+}
+
+///A draw call handed to the `Renderer`, in screen coordinates and with the opacity applied.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DrawCommand {
+    Rectangle { rect: Rect, color: Color },
+    Circle { center: Vec2, radius: f32, color: Color },
+    Text { text: String, position: Vec2, font_size: f32, color: Color },
+    ///Drawing is limited to the rectangle from here on, or not limited anymore with `None`.
+    Clip(Option<Rect>),
+}
+impl DrawCommand {
+    fn execute(&self, renderer: &mut dyn Renderer) {
+        match self {
+            DrawCommand::Rectangle { rect, color } => renderer.draw_rectangle(*rect, *color),
+            DrawCommand::Circle { center, radius, color } => renderer.draw_circle(*center, *radius, *color),
+            DrawCommand::Text { text, position, font_size, color } => renderer.draw_text(text, *position, *font_size, *color),
+            DrawCommand::Clip(clip) => renderer.set_clip(*clip),
+        }
+    }
+}
+
+// Written with two decimals, so lists recorded on different machines compare equal as text.
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn hex(color: &Color) -> String {
+            let [r, g, b, a]: [u8; 4] = (*color).into();
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+
+        match self {
+            DrawCommand::Rectangle { rect, color } => write!(f, "rectangle {:.2} {:.2} {:.2} {:.2} {}", rect.x, rect.y, rect.w, rect.h, hex(color)),
+            DrawCommand::Circle { center, radius, color } => write!(f, "circle {:.2} {:.2} {:.2} {}", center.x, center.y, radius, hex(color)),
+            DrawCommand::Text { text, position, font_size, color } => write!(f, "text {:?} {:.2} {:.2} {:.2} {}", text, position.x, position.y, font_size, hex(color)),
+            DrawCommand::Clip(Some(clip)) => write!(f, "clip {:.2} {:.2} {:.2} {:.2}", clip.x, clip.y, clip.w, clip.h),
+            DrawCommand::Clip(None) => write!(f, "clip none"),
+        }
+    }
+}
+
+///The draw calls made while recording, in order, see **record**.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct DisplayList {
+    pub commands: Vec<DrawCommand>,
+}
+impl DisplayList {
+    ///Draw the recorded commands again with `renderer`.
+    pub fn replay(&self, renderer: &mut dyn Renderer) {
+        for command in &self.commands {
+            command.execute(renderer);
+        }
+    }
+}
+impl fmt::Display for DisplayList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for command in &self.commands {
+            writeln!(f, "{command}")?;
+        }
+        Ok(())
+    }
+}
+
+// A draw queued with **draw_later**, with the transform and opacity to run it with.
+type Deferred = (Transform, f32, Box<dyn FnOnce()>);
+
+thread_local! {
+    // Set while recording a display list.
+    static RECORDING: RefCell<Option<Vec<DrawCommand>>> = const { RefCell::new(None) };
+    static RENDERER: RefCell<Box<dyn Renderer>> = RefCell::new(Box::new(MacroquadRenderer));
+    static OPACITY: Cell<f32> = const { Cell::new(1.0) };
+    static UI_SCALE: Cell<f32> = const { Cell::new(1.0) };
+    static TRANSFORM: Cell<Transform> = const { Cell::new(Transform{ scale: 1.0, offset: Vec2::ZERO }) };
+    // The area drawing is limited to, on the screen.
+    static CLIP: Cell<Option<Rect>> = const { Cell::new(None) };
+    // Drawn at the end of the root menu being drawn, with the transform and opacity they were queued with.
+    static DEFERRED: RefCell<Vec<Deferred>> = RefCell::new(Vec::new());
+    // Text measured by the renderer, by pixel size and text.
+    static TEXT_SIZES: RefCell<HashMap<u16, HashMap<String, TextDimensions>>> = RefCell::new(HashMap::new());
+}
+
+///Draw every menu on this thread with `renderer` from now on, returning the one used until now.
+pub fn set_renderer<R: Renderer + 'static>(renderer: R) -> Box<dyn Renderer> {
+    replace_renderer(Box::new(renderer))
+}
+
+///Same as **set_renderer**, for putting back the renderer it returned.
+pub(crate) fn replace_renderer(renderer: Box<dyn Renderer>) -> Box<dyn Renderer> {
+    // Text measured by the previous renderer may not match the new one.
+    TEXT_SIZES.with(|sizes| sizes.borrow_mut().clear());
+    RENDERER.with(|current| current.replace(renderer))
+}
+
+fn with_renderer<R>(f: impl FnOnce(&mut dyn Renderer) -> R) -> R {
+    RENDERER.with(|renderer| f(renderer.borrow_mut().as_mut()))
+}
+
+///Run `f`, like **Menu::draw**, and return everything it drew. It is still drawn by the current renderer.
+pub fn record(f: impl FnOnce()) -> DisplayList {
+    let previous = RECORDING.with(|recording| recording.replace(Some(vec![])));
+    f();
+    let commands = RECORDING.with(|recording| recording.replace(previous)).unwrap_or_default();
+
+    DisplayList{ commands }
+}
+
+fn submit(command: DrawCommand) {
+    with_renderer(|renderer| command.execute(renderer));
+    RECORDING.with(|recording| {
+        if let Some(commands) = recording.borrow_mut().as_mut() {
+            commands.push(command);
+        }
+    });
+}
+
+// Past this many measured texts at one size the cache starts over, so changing texts like a FPS counter don't pile up.
+const TEXT_CACHE_SIZE: usize = 1024;
+
+// Maps the coordinates elements are laid out in to the screen, before the UI scale: `point * scale + offset`.
+#[derive(Clone, Copy)]
+struct Transform {
+    scale: f32,
+    offset: Vec2,
+}
+
+///Set the scale every menu is drawn and hit tested at, e.g. `2.0` on a TV or as an accessibility option.
+///
+///Defaults to `1.0`. macroquad already measures the screen in points on `high_dpi` windows, so this
+///follows the display's DPI scale, and anything set here comes on top of it.
+pub fn set_ui_scale(scale: f32) {
+    UI_SCALE.with(|current| current.set(scale));
+}
+
+pub fn ui_scale() -> f32 {
+    UI_SCALE.with(|current| current.get())
+}
+
+///Run `f` with everything drawn, measured and hit tested in it scaled by `scale` around `pivot`.
+pub(crate) fn with_scale<R>(scale: f32, pivot: Vec2, f: impl FnOnce() -> R) -> R {
+    let previous = TRANSFORM.with(|current| {
+        let previous = current.get();
+        // pivot + (point - pivot) * scale, then through the transform already applied.
+        current.set(Transform{
+            scale: previous.scale * scale,
+            offset: previous.offset + pivot * (1.0 - scale) * previous.scale,
+        });
+        previous
+    });
+    let result = f();
+    TRANSFORM.with(|current| current.set(previous));
+
+    result
+}
+
+///Return how many screen units one layout unit currently covers.
+pub(crate) fn screen_scale() -> f32 {
+    TRANSFORM.with(|current| current.get().scale) * ui_scale()
+}
+
+fn to_screen(point: Vec2) -> Vec2 {
+    let transform = TRANSFORM.with(|current| current.get());
+    (point * transform.scale + transform.offset) * ui_scale()
+}
+
+///Bring a point on the screen, like the mouse position, back into layout coordinates.
+pub(crate) fn from_screen(point: Vec2) -> Vec2 {
+    let transform = TRANSFORM.with(|current| current.get());
+    (point / ui_scale() - transform.offset) / transform.scale
+}
+
+///Run `f` with drawing limited to `rect`, and pointers outside of it ignored, within any clip already applied.
+pub(crate) fn with_clip<R>(rect: Rect, f: impl FnOnce() -> R) -> R {
+    let mut clip = rect_to_screen(rect);
+    let previous = CLIP.with(|current| current.get());
+    if let Some(previous) = previous {
+        clip = previous.intersect(clip).unwrap_or(Rect::new(clip.x, clip.y, 0.0, 0.0));
+    }
+
+    CLIP.with(|current| current.set(Some(clip)));
+    apply_scissor(Some(clip));
+    let result = f();
+    CLIP.with(|current| current.set(previous));
+    apply_scissor(previous);
+
+    result
+}
+
+///Run `draw` once the root menu being drawn is done, so it shows above the rest of the menu,
+///like the list of an open dropdown. It keeps the current scale and opacity, but not the clip.
+pub(crate) fn draw_later(draw: impl FnOnce() + 'static) {
+    let transform = TRANSFORM.with(|current| current.get());
+    let opacity = OPACITY.with(|current| current.get());
+    DEFERRED.with(|deferred| deferred.borrow_mut().push((transform, opacity, Box::new(draw))));
+}
+
+///Run what was queued with **draw_later**, in order.
+pub(crate) fn draw_deferred() {
+    let deferred = DEFERRED.with(|deferred| std::mem::take(&mut *deferred.borrow_mut()));
+    for (transform, opacity, draw) in deferred {
+        let previous = (TRANSFORM.with(|current| current.replace(transform)), OPACITY.with(|current| current.replace(opacity)));
+        draw();
+        TRANSFORM.with(|current| current.set(previous.0));
+        OPACITY.with(|current| current.set(previous.1));
+    }
+}
+
+///Return whether a point on the screen is outside of the current clip.
+pub(crate) fn is_clipped(point: Vec2) -> bool {
+    CLIP.with(|current| current.get()).is_some_and(|clip| !clip.contains(point))
+}
+
+fn apply_scissor(clip: Option<Rect>) {
+    submit(DrawCommand::Clip(clip))
+}
+
+///Run `f` with every draw call faded by `opacity`, on top of any opacity already applied.
+pub(crate) fn with_opacity<R>(opacity: f32, f: impl FnOnce() -> R) -> R {
+    let previous = OPACITY.with(|current| current.replace(current.get() * opacity));
+    let result = f();
+    OPACITY.with(|current| current.set(previous));
+
+    result
+}
+
+fn faded(color: Color) -> Color {
+    Color{
+        a: color.a * OPACITY.with(|current| current.get()),
+        ..color
+    }
+}
+
+pub(crate) fn draw_rectangle(x: f32, y: f32, w: f32, h: f32, color: Color) {
+    let rect = rect_to_screen(Rect::new(x, y, w, h));
+    submit(DrawCommand::Rectangle{ rect, color: faded(color) })
+}
+
+///Draw the outline of `rect`, one unit wide on the screen whatever the scale.
+pub(crate) fn draw_rectangle_lines(rect: Rect, color: Color) {
+    let thickness = 1.0 / screen_scale();
+    let inner_height = (rect.h - thickness * 2.0).max(0.0);
+    draw_rectangle(rect.x, rect.y, rect.w, thickness, color);
+    draw_rectangle(rect.x, rect.bottom() - thickness, rect.w, thickness, color);
+    draw_rectangle(rect.x, rect.y + thickness, thickness, inner_height, color);
+    draw_rectangle(rect.right() - thickness, rect.y + thickness, thickness, inner_height, color);
+}
+
+pub(crate) fn draw_circle(x: f32, y: f32, r: f32, color: Color) {
+    submit(DrawCommand::Circle{ center: to_screen(vec2(x, y)), radius: r * screen_scale(), color: faded(color) })
+}
+
+pub(crate) fn draw_text(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+    submit(DrawCommand::Text{
+        text: text.to_string(),
+        position: to_screen(vec2(x, y)),
+        font_size: font_size * screen_scale(),
+        color: faded(color),
+    })
+}
+
+///Return the size of the screen, as given by the renderer.
+pub(crate) fn screen_size() -> Option<Vec2> {
+    with_renderer(|renderer| renderer.screen_size())
+}
+
+///Measure `text` at the size it is drawn at, in layout units.
+///
+///Measurements are cached, since titles are measured on every layout.
+pub(crate) fn measure_text(text: &str, font_size: u16) -> TextDimensions {
+    let scale = screen_scale();
+    let pixel_size = (font_size as f32 * scale).round() as u16;
+    let dimensions = TEXT_SIZES.with(|sizes| {
+        let mut sizes = sizes.borrow_mut();
+        let sizes = sizes.entry(pixel_size).or_default();
+        if let Some(dimensions) = sizes.get(text) {
+            return *dimensions;
+        }
+        if sizes.len() >= TEXT_CACHE_SIZE {
+            sizes.clear();
+        }
+        let dimensions = with_renderer(|renderer| renderer.measure_text(text, pixel_size));
+        sizes.insert(text.to_string(), dimensions);
+        dimensions
+    });
+
+    TextDimensions{
+        width: dimensions.width / scale,
+        height: dimensions.height / scale,
+        offset_y: dimensions.offset_y / scale,
+    }
+}
+
+///Map a rectangle in layout coordinates to the screen.
+pub(crate) fn rect_to_screen(rect: Rect) -> Rect {
+    let position = to_screen(rect.point());
+    let scale = screen_scale();
+    Rect::new(position.x, position.y, rect.w * scale, rect.h * scale)
+}
@@ -0,0 +1,224 @@
+//! Record the input a menu updates with, and play it back later, e.g. to reproduce a bug report
+//! or as a regression test.
+//!
+//! Each frame is recorded along with the time step it was updated with, which drives the menu
+//! again on replay, so long-presses and animations play out the same whatever the frame rate.
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+use macroquad::prelude::*;
+use crate::input::{InputSource, KeyEvent, KeyInput, PointerKind, RawPointer, RECORDED_KEYS};
+
+///The input of one update of a menu.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RecordedFrame {
+    ///The seconds since the previous update.
+    pub delta: f32,
+    pub pointers: Vec<RawPointer>,
+    pub keys: KeyInput,
+}
+
+///The input of a menu over a run of updates, see **Menu::start_recording**.
+///
+///Saved as text, one `frame` line per update followed by a line per pointer, typed text and key.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+impl InputRecording {
+    pub fn new() -> InputRecording {
+        InputRecording::default()
+    }
+
+    ///Write the recording in its text format.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for frame in &self.frames {
+            text.push_str(&format!("frame {}\n", frame.delta));
+            for pointer in &frame.pointers {
+                let kind = match pointer.kind {
+                    PointerKind::Mouse => "mouse",
+                    PointerKind::Touch => "touch",
+                };
+                text.push_str(&format!("pointer {} {} {} {}", pointer.id, kind, pointer.position.x, pointer.position.y));
+                let flags = [
+                    ("down", pointer.down), ("pressed", pointer.pressed), ("released", pointer.released),
+                    ("right-down", pointer.right.down), ("right-pressed", pointer.right.pressed), ("right-released", pointer.right.released),
+                    ("middle-down", pointer.middle.down), ("middle-pressed", pointer.middle.pressed), ("middle-released", pointer.middle.released),
+                ];
+                for (flag, is_set) in flags {
+                    if is_set {
+                        text.push(' ');
+                        text.push_str(flag);
+                    }
+                }
+                text.push('\n');
+            }
+            // Characters typed one after the other share a line, keys go in between them in order.
+            let mut typed = String::new();
+            for event in &frame.keys.events {
+                match event {
+                    KeyEvent::Char(character) => typed.push(*character),
+                    KeyEvent::Key(key) => {
+                        if !typed.is_empty() {
+                            text.push_str(&format!("typed {}\n", escape(&std::mem::take(&mut typed))));
+                        }
+                        text.push_str(&format!("key {:?}\n", key));
+                    }
+                }
+            }
+            if !typed.is_empty() {
+                text.push_str(&format!("typed {}\n", escape(&typed)));
+            }
+        }
+        text
+    }
+
+    ///Read a recording written by **to_text**. Blank lines and lines starting with `#` are skipped.
+    pub fn from_text(text: &str) -> io::Result<InputRecording> {
+        let mut recording = InputRecording::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |expected: &str| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: expected {}", index + 1, expected));
+            let (command, arguments) = line.split_once(' ').unwrap_or((line, ""));
+            if command == "frame" {
+                let delta = arguments.parse().map_err(|_| invalid("`frame <seconds>`"))?;
+                recording.frames.push(RecordedFrame{ delta, ..RecordedFrame::default() });
+                continue;
+            }
+
+            let Some(frame) = recording.frames.last_mut() else {
+                return Err(invalid("a `frame` line first"));
+            };
+            match command {
+                "pointer" => {
+                    let pointer = parse_pointer(arguments).ok_or_else(|| invalid("`pointer <id> <mouse|touch> <x> <y> [flags]`"))?;
+                    frame.pointers.push(pointer);
+                }
+                "typed" => frame.keys.events.extend(unescape(arguments).chars().map(KeyEvent::Char)),
+                "key" => {
+                    let key = RECORDED_KEYS.iter().find(|key| format!("{:?}", key) == arguments).ok_or_else(|| invalid("a key menus react to"))?;
+                    frame.keys.events.push(KeyEvent::Key(*key));
+                }
+                _ => return Err(invalid("`frame`, `pointer`, `typed` or `key`")),
+            }
+        }
+        Ok(recording)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<InputRecording> {
+        InputRecording::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+fn parse_pointer(arguments: &str) -> Option<RawPointer> {
+    let mut words = arguments.split(' ');
+    let id = words.next()?.parse().ok()?;
+    let kind = match words.next()? {
+        "mouse" => PointerKind::Mouse,
+        "touch" => PointerKind::Touch,
+        _ => return None,
+    };
+    let position = vec2(words.next()?.parse().ok()?, words.next()?.parse().ok()?);
+    let mut pointer = RawPointer::new(id, kind, position);
+    for flag in words {
+        match flag {
+            "down" => pointer.down = true,
+            "pressed" => pointer.pressed = true,
+            "released" => pointer.released = true,
+            "right-down" => pointer.right.down = true,
+            "right-pressed" => pointer.right.pressed = true,
+            "right-released" => pointer.right.released = true,
+            "middle-down" => pointer.middle.down = true,
+            "middle-pressed" => pointer.middle.pressed = true,
+            "middle-released" => pointer.middle.released = true,
+            _ => return None,
+        }
+    }
+    Some(pointer)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        match (character, characters.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                characters.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                characters.next();
+            }
+            _ => unescaped.push(character),
+        }
+    }
+    unescaped
+}
+
+struct ReplayState {
+    recording: InputRecording,
+    // The frame the next update reads.
+    next: usize,
+}
+
+///Plays an `InputRecording` back into a menu, see **Menu::replay**.
+///
+///Once every frame was played, the menu sees no input and no time passing.
+#[derive(Clone)]
+pub struct Replay {
+    state: Rc<RefCell<ReplayState>>,
+}
+impl Replay {
+    pub(crate) fn new(recording: InputRecording) -> Replay {
+        Replay{
+            state: Rc::new(RefCell::new(ReplayState{ recording, next: 0 })),
+        }
+    }
+
+    ///Return the time step of the frame played next, the menu's clock on replay.
+    pub(crate) fn delta(&self) -> f32 {
+        let state = self.state.borrow();
+        state.recording.frames.get(state.next).map_or(0.0, |frame| frame.delta)
+    }
+
+    ///Return how many frames were played.
+    pub fn frames_played(&self) -> usize {
+        self.state.borrow().next
+    }
+
+    pub fn is_finished(&self) -> bool {
+        let state = self.state.borrow();
+        state.next >= state.recording.frames.len()
+    }
+}
+impl InputSource for Replay {
+    fn poll(&mut self) -> Vec<RawPointer> {
+        let mut state = self.state.borrow_mut();
+        let pointers = state.recording.frames.get(state.next).map(|frame| frame.pointers.clone()).unwrap_or_default();
+        state.next += 1;
+        pointers
+    }
+
+    // Read after **poll**, which moved on to the next frame.
+    fn poll_keys(&mut self) -> Option<KeyInput> {
+        let state = self.state.borrow();
+        let keys = state.next.checked_sub(1)
+            .and_then(|played| state.recording.frames.get(played))
+            .map(|frame| frame.keys.clone());
+        Some(keys.unwrap_or_default())
+    }
+}
@@ -0,0 +1,211 @@
+//! Draw menus on the CPU into an image, for screenshots and pixel tests on machines without a GPU.
+//!
+//! Text is drawn with the font macroquad uses by default, rasterized and measured the same way,
+//! so menus are laid out exactly like on the screen:
+//!
+//! ```no_run
+//! # let menu = Simples_menu::Menu::new("Menu".to_string(), Simples_menu::macroquad::prelude::Vec2::ZERO);
+//! Simples_menu::software::screenshot(&menu, 800, 600).save_png("menu.png").unwrap();
+//! ```
+//!
+//! Needs the `software` feature. The font is ProggyClean by Tristan Grimmer, under the MIT
+//! license found in `src/ProggyClean.LICENSE.txt`.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use fontdue::{Font, FontSettings, Metrics};
+use macroquad::prelude::{Color, Rect, Vec2};
+use macroquad::text::TextDimensions;
+use tiny_skia::{FillRule, Mask, Paint, PathBuilder, Pixmap, PixmapPaint, Transform};
+use crate::Menu;
+use crate::render::{replace_renderer, set_renderer, Renderer};
+
+// The default font of macroquad, see `ProggyClean.LICENSE.txt` next to it.
+const DEFAULT_FONT: &[u8] = include_bytes!("ProggyClean.ttf");
+
+struct Canvas {
+    pixmap: Pixmap,
+    clip: Option<Mask>,
+    font: Font,
+    // Rasterized glyphs, by character and pixel size.
+    glyphs: HashMap<(char, u16), (Metrics, Vec<u8>)>,
+}
+impl Canvas {
+    fn glyph(&mut self, character: char, font_size: u16) -> &(Metrics, Vec<u8>) {
+        let font = &self.font;
+        self.glyphs.entry((character, font_size))
+            .or_insert_with(|| font.rasterize(character, font_size as f32))
+    }
+}
+
+///A `Renderer` drawing into an RGBA image.
+///
+///Clones draw into the same image, so one can be given to **render::set_renderer** and the other
+///kept to read the result.
+#[derive(Clone)]
+pub struct SoftwareRenderer {
+    canvas: Rc<RefCell<Canvas>>,
+}
+impl SoftwareRenderer {
+    ///Create a renderer drawing into a transparent image of `width` by `height` pixels.
+    pub fn new(width: u32, height: u32) -> SoftwareRenderer {
+        SoftwareRenderer{
+            canvas: Rc::new(RefCell::new(Canvas{
+                pixmap: Pixmap::new(width.max(1), height.max(1)).expect("the image size is not zero"),
+                clip: None,
+                font: Font::from_bytes(DEFAULT_FONT, FontSettings::default()).expect("the default font is valid"),
+                glyphs: HashMap::new(),
+            })),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.canvas.borrow().pixmap.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.canvas.borrow().pixmap.height()
+    }
+
+    ///Fill the whole image with `color`.
+    pub fn clear(&self, color: Color) {
+        self.canvas.borrow_mut().pixmap.fill(to_skia(color));
+    }
+
+    ///Return the image as rows of RGBA bytes, top to bottom.
+    pub fn pixels(&self) -> Vec<u8> {
+        self.canvas.borrow().pixmap.pixels().iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect()
+    }
+
+    ///Return the color of the pixel at `x`, `y`, or `None` outside of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let canvas = self.canvas.borrow();
+        let pixel = canvas.pixmap.pixel(x, y)?.demultiply();
+        Some(Color::from_rgba(pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()))
+    }
+
+    ///Return the image encoded as a PNG.
+    pub fn encode_png(&self) -> std::io::Result<Vec<u8>> {
+        self.canvas.borrow().pixmap.encode_png().map_err(std::io::Error::other)
+    }
+
+    ///Save the image as a PNG file.
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.encode_png()?)
+    }
+}
+impl Renderer for SoftwareRenderer {
+    fn draw_rectangle(&mut self, rect: Rect, color: Color) {
+        let Some(rect) = tiny_skia::Rect::from_xywh(rect.x, rect.y, rect.w, rect.h) else {
+            return;
+        };
+        let canvas = &mut *self.canvas.borrow_mut();
+        canvas.pixmap.fill_rect(rect, &paint(color, false), Transform::identity(), canvas.clip.as_ref());
+    }
+
+    fn draw_circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        let Some(path) = PathBuilder::from_circle(center.x, center.y, radius) else {
+            return;
+        };
+        let canvas = &mut *self.canvas.borrow_mut();
+        canvas.pixmap.fill_path(&path, &paint(color, true), FillRule::Winding, Transform::identity(), canvas.clip.as_ref());
+    }
+
+    // Each glyph is placed like macroquad's `draw_text` places it, from its baseline.
+    fn draw_text(&mut self, text: &str, position: Vec2, font_size: f32, color: Color) {
+        let canvas = &mut *self.canvas.borrow_mut();
+        let font_size = font_size as u16;
+        let [red, green, blue, alpha]: [u8; 4] = color.into();
+
+        let mut advance = 0.0;
+        for character in text.chars() {
+            let (metrics, coverage) = canvas.glyph(character, font_size);
+            let (x, y) = (
+                (position.x + metrics.xmin as f32 + advance).round() as i32,
+                (position.y - metrics.height as f32 - metrics.ymin as f32).round() as i32,
+            );
+            advance += metrics.advance_width;
+
+            let glyph_pixels: Vec<u8> = coverage.iter()
+                .flat_map(|coverage| {
+                    // Premultiplied, as tiny-skia stores its pixels.
+                    let alpha = (alpha as u32 * *coverage as u32 / 255) as u8;
+                    let premultiply = |channel: u8| (channel as u32 * alpha as u32 / 255) as u8;
+                    [premultiply(red), premultiply(green), premultiply(blue), alpha]
+                })
+                .collect();
+            let Some(size) = tiny_skia::IntSize::from_wh(metrics.width as u32, metrics.height as u32) else {
+                continue;
+            };
+            let glyph = Pixmap::from_vec(glyph_pixels, size).expect("the glyph has a pixel per coverage value");
+            canvas.pixmap.draw_pixmap(x, y, glyph.as_ref(), &PixmapPaint::default(), Transform::identity(), canvas.clip.as_ref());
+        }
+    }
+
+    // The same measurements as macroquad's `measure_text`, on a screen without DPI scaling.
+    fn measure_text(&mut self, text: &str, font_size: u16) -> TextDimensions {
+        let canvas = &mut *self.canvas.borrow_mut();
+        let mut width = 0.0;
+        let mut min_y = f32::MAX;
+        let mut max_y = -f32::MAX;
+        for character in text.chars() {
+            let (metrics, _) = canvas.glyph(character, font_size);
+            width += metrics.advance_width;
+            min_y = min_y.min(metrics.ymin as f32);
+            max_y = max_y.max(metrics.height as f32 + metrics.ymin as f32);
+        }
+
+        TextDimensions{
+            width,
+            height: max_y - min_y,
+            offset_y: max_y,
+        }
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) {
+        let canvas = &mut *self.canvas.borrow_mut();
+        canvas.clip = clip.and_then(|clip| {
+            let rect = tiny_skia::Rect::from_xywh(clip.x, clip.y, clip.w.max(0.0), clip.h.max(0.0))?;
+            let mut mask = Mask::new(canvas.pixmap.width(), canvas.pixmap.height())?;
+            mask.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+            Some(mask)
+        });
+        // An empty clip hides everything rather than nothing.
+        if clip.is_some() && canvas.clip.is_none() {
+            canvas.clip = Mask::new(canvas.pixmap.width(), canvas.pixmap.height());
+        }
+    }
+
+    fn screen_size(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.width() as f32, self.height() as f32))
+    }
+}
+
+fn to_skia(color: Color) -> tiny_skia::Color {
+    let [red, green, blue, alpha]: [u8; 4] = color.into();
+    tiny_skia::Color::from_rgba8(red, green, blue, alpha)
+}
+
+fn paint(color: Color, anti_alias: bool) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(to_skia(color));
+    paint.anti_alias = anti_alias;
+    paint
+}
+
+///Draw `menu` into a transparent image of `width` by `height` pixels, laying it out with the
+///same font metrics, and go back to the renderer used before.
+pub fn screenshot(menu: &Menu, width: u32, height: u32) -> SoftwareRenderer {
+    let renderer = SoftwareRenderer::new(width, height);
+    let previous = set_renderer(renderer.clone());
+    menu.draw();
+    crate::tooltip::draw_overlay();
+    replace_renderer(previous);
+
+    renderer
+}
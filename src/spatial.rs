@@ -0,0 +1,65 @@
+//! A uniform grid over the elements of a menu, to find the one under a pointer without
+//! checking every element.
+use std::collections::HashMap;
+use macroquad::prelude::*;
+
+// Rectangles spanning more cells than this, like a nested menu around many small elements,
+// are kept aside and checked on every lookup instead.
+const MAX_CELLS_PER_RECT: i64 = 64;
+
+///Finds which of a list of rectangles is on top at a point, the last one in the list winning.
+pub struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    large: Vec<usize>,
+    rects: Vec<Option<Rect>>,
+}
+impl SpatialIndex {
+    ///Build the index over `rects`, which are found by their position in the list.
+    ///`None`s, like hidden elements, are never found.
+    pub fn new(rects: Vec<Option<Rect>>) -> SpatialIndex {
+        // Cells about the size of an average element keep each of them in a cell or two.
+        let (count, total) = rects.iter().flatten()
+            .fold((0, Vec2::ZERO), |(count, total), rect| (count + 1, total + rect.size()));
+        let average = if count == 0 { Vec2::ONE } else { total / count as f32 };
+        let cell_size = average.max_element().max(1.0);
+
+        let mut index = SpatialIndex{
+            cell_size,
+            cells: HashMap::new(),
+            large: vec![],
+            rects,
+        };
+        for (position, rect) in index.rects.iter().enumerate() {
+            let Some(rect) = rect else {
+                continue;
+            };
+            let (min, max) = (index.cell(rect.point()), index.cell(rect.point() + rect.size()));
+            if (max.0 as i64 - min.0 as i64 + 1).saturating_mul(max.1 as i64 - min.1 as i64 + 1) > MAX_CELLS_PER_RECT {
+                index.large.push(position);
+                continue;
+            }
+            for x in min.0..=max.0 {
+                for y in min.1..=max.1 {
+                    index.cells.entry((x, y)).or_default().push(position);
+                }
+            }
+        }
+
+        index
+    }
+
+    fn cell(&self, point: Vec2) -> (i32, i32) {
+        ((point.x / self.cell_size).floor() as i32, (point.y / self.cell_size).floor() as i32)
+    }
+
+    ///Return the position in the list of the last rectangle containing `point`.
+    pub fn find(&self, point: Vec2) -> Option<usize> {
+        let contains = |position: &usize| self.rects[*position].is_some_and(|rect| rect.contains(point));
+        let in_cell = self.cells.get(&self.cell(point))
+            .and_then(|candidates| candidates.iter().rev().copied().find(contains));
+        let large = self.large.iter().rev().copied().find(contains);
+
+        in_cell.max(large)
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use macroquad::prelude::*;
+
+///The user-facing state of a menu tree, keyed by element names.
+///
+///Elements are keyed by the path of names leading to them, like `options.vsync`, and the
+///properties of the menus themselves by their path followed by `:`, like `options:position`.
+///Saved as one `key = value` line per entry.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct MenuState {
+    values: BTreeMap<String, String>,
+}
+impl MenuState {
+    pub fn new() -> MenuState {
+        MenuState::default()
+    }
+
+    pub fn set_bool(&mut self, key: &str, value: bool) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.values.get(key)?.parse().ok()
+    }
+
+    pub fn set_f32(&mut self, key: &str, value: f32) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+    pub fn get_f32(&self, key: &str) -> Option<f32> {
+        self.values.get(key)?.parse().ok()
+    }
+
+    pub fn set_vec2(&mut self, key: &str, value: Vec2) {
+        self.values.insert(key.to_string(), format!("{} {}", value.x, value.y));
+    }
+    pub fn get_vec2(&self, key: &str) -> Option<Vec2> {
+        let (x, y) = self.values.get(key)?.split_once(' ')?;
+        Some(vec2(x.parse().ok()?, y.parse().ok()?))
+    }
+
+    pub fn set_text(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+    pub fn get_text(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    ///Write the state in the `key = value` format, one entry per line.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (key, value) in &self.values {
+            text.push_str(&escape(key));
+            text.push_str(" = ");
+            text.push_str(&escape(value));
+            text.push('\n');
+        }
+        text
+    }
+
+    ///Read a state written by **to_text**. Blank lines and lines starting with `#` are skipped.
+    pub fn from_text(text: &str) -> io::Result<MenuState> {
+        let mut state = MenuState::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(" = ") else {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line {}: expected `key = value`", index + 1)));
+            };
+            state.values.insert(unescape(key), unescape(value));
+        }
+        Ok(state)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<MenuState> {
+        MenuState::from_text(&fs::read_to_string(path)?)
+    }
+}
+
+///Join the key of an element to the path of the menu holding it.
+pub(crate) fn child_key(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+///Return the key of a property of the menu at `path`.
+pub(crate) fn property_key(path: &str, property: &str) -> String {
+    format!("{}:{}", path, property)
+}
+
+// Every `=` and `#` is escaped, so the first ` = ` on a line always splits the key from the
+// value and no line reads as a comment, and line breaks are escaped so each entry stays on one line.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '=' => escaped.push_str("\\="),
+            '#' => escaped.push_str("\\#"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
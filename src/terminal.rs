@@ -0,0 +1,420 @@
+//! Show menus in a terminal, drawn with character cells and driven by the terminal's keyboard and mouse.
+//!
+//! The menus are laid out as usual, on a screen where every character cell is `CELL_SIZE` large
+//! and every character of text takes one cell, whatever its font size. Rectangles color the
+//! cells they cover, and circles, like the mark of a checked `CheckBox`, become a `●`:
+//!
+//! ```no_run
+//! use Simples_menu::{Menu, Button, PositionType};
+//! use Simples_menu::macroquad::prelude::*;
+//! use Simples_menu::terminal::Terminal;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! // Started first, elements measure their text with it when built.
+//! let mut terminal = Terminal::start()?;
+//! let mut menu = Menu::new("Server".to_string(), vec2(40.0, 40.0));
+//! let quit = menu.add_element(Button::new("Quit".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+//! terminal.attach(&mut menu);
+//!
+//! while !quit.read().has_been_pressed {
+//!     let keys = terminal.read_input()?;
+//!     if keys.pressed(KeyCode::Escape) {
+//!         break;
+//!     }
+//!     menu.update();
+//!     terminal.clear();
+//!     menu.draw();
+//!     terminal.present()?;
+//!     std::thread::sleep(std::time::Duration::from_millis(16));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Needs the `terminal` feature.
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyCode as TermKey, KeyEventKind, KeyModifiers, MouseButton as TermButton, MouseEventKind};
+use crossterm::{cursor, style, terminal, QueueableCommand};
+use macroquad::prelude::*;
+use macroquad::text::TextDimensions;
+use crate::Menu;
+use crate::input::{ButtonState, InputSource, KeyEvent, KeyInput, PointerKind, RawPointer, MOUSE_ID};
+use crate::render::{replace_renderer, set_renderer, Renderer};
+
+pub use crossterm;
+
+///How large one character cell is on the screen the menus are laid out on.
+pub const CELL_SIZE: Vec2 = Vec2{ x: 8.0, y: 16.0 };
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct TerminalCell {
+    character: char,
+    // `None` leaves the terminal's own colors.
+    foreground: Option<[u8; 3]>,
+    background: Option<[u8; 3]>,
+}
+const EMPTY_CELL: TerminalCell = TerminalCell{ character: ' ', foreground: None, background: None };
+
+struct Screen {
+    columns: u16,
+    rows: u16,
+    cells: Vec<TerminalCell>,
+    clip: Option<Rect>,
+}
+impl Screen {
+    ///Return the cell at `column`, `row`, unless it is off the screen or clipped.
+    fn cell_mut(&mut self, column: i32, row: i32) -> Option<&mut TerminalCell> {
+        if column < 0 || row < 0 || column >= self.columns as i32 || row >= self.rows as i32 {
+            return None;
+        }
+        if let Some(clip) = self.clip {
+            if !clip.contains(cell_center(column, row)) {
+                return None;
+            }
+        }
+        self.cells.get_mut(row as usize * self.columns as usize + column as usize)
+    }
+}
+
+fn cell_center(column: i32, row: i32) -> Vec2 {
+    Vec2{
+        x: (column as f32 + 0.5) * CELL_SIZE.x,
+        y: (row as f32 + 0.5) * CELL_SIZE.y,
+    }
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    let [red, green, blue, _]: [u8; 4] = color.into();
+    [red, green, blue]
+}
+
+///Lay `color` over `under`, the terminal's colors being taken as black.
+fn blend(color: Color, under: Option<[u8; 3]>) -> [u8; 3] {
+    let under = under.unwrap_or([0, 0, 0]);
+    let over = rgb(color);
+    let mix = |index: usize| (over[index] as f32 * color.a + under[index] as f32 * (1.0 - color.a)).round() as u8;
+    [mix(0), mix(1), mix(2)]
+}
+
+///A `Renderer` drawing into a grid of character cells.
+///
+///Clones draw into the same cells, so one can be given to **render::set_renderer** and the other
+///kept to show the result.
+#[derive(Clone)]
+pub struct TerminalRenderer {
+    screen: Rc<RefCell<Screen>>,
+}
+impl TerminalRenderer {
+    pub fn new(columns: u16, rows: u16) -> TerminalRenderer {
+        TerminalRenderer{
+            screen: Rc::new(RefCell::new(Screen{
+                columns,
+                rows,
+                cells: vec![EMPTY_CELL; columns as usize * rows as usize],
+                clip: None,
+            })),
+        }
+    }
+
+    ///Change the size of the grid, clearing it.
+    pub fn resize(&self, columns: u16, rows: u16) {
+        let mut screen = self.screen.borrow_mut();
+        screen.columns = columns;
+        screen.rows = rows;
+        screen.cells = vec![EMPTY_CELL; columns as usize * rows as usize];
+    }
+
+    ///Empty every cell.
+    pub fn clear(&self) {
+        self.screen.borrow_mut().cells.fill(EMPTY_CELL);
+    }
+
+    ///Return the characters of the grid, one line per row.
+    pub fn text(&self) -> String {
+        let screen = self.screen.borrow();
+        screen.cells.chunks(screen.columns.max(1) as usize)
+            .map(|row| row.iter().map(|cell| cell.character).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    ///Write the whole grid to `out`, a terminal in raw mode.
+    pub fn present<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        let screen = self.screen.borrow();
+        let to_terminal = |color: Option<[u8; 3]>| color.map_or(style::Color::Reset, |[r, g, b]| style::Color::Rgb{ r, g, b });
+
+        out.queue(cursor::MoveTo(0, 0))?;
+        let mut colors = None;
+        for (row, cells) in screen.cells.chunks(screen.columns.max(1) as usize).enumerate() {
+            out.queue(cursor::MoveTo(0, row as u16))?;
+            for cell in cells {
+                // Only send the colors when they change, most cells share them with the previous one.
+                if colors != Some((cell.foreground, cell.background)) {
+                    out.queue(style::SetForegroundColor(to_terminal(cell.foreground)))?;
+                    out.queue(style::SetBackgroundColor(to_terminal(cell.background)))?;
+                    colors = Some((cell.foreground, cell.background));
+                }
+                out.queue(style::Print(cell.character))?;
+            }
+        }
+        out.queue(style::ResetColor)?;
+        out.flush()
+    }
+}
+impl Renderer for TerminalRenderer {
+    // Colors the cells whose center is in the rectangle, hiding the text under them.
+    fn draw_rectangle(&mut self, rect: Rect, color: Color) {
+        let mut screen = self.screen.borrow_mut();
+        let first = ((rect.x / CELL_SIZE.x).floor() as i32, (rect.y / CELL_SIZE.y).floor() as i32);
+        let last = ((rect.right() / CELL_SIZE.x).ceil() as i32, (rect.bottom() / CELL_SIZE.y).ceil() as i32);
+        for row in first.1..last.1 {
+            for column in first.0..last.0 {
+                if !rect.contains(cell_center(column, row)) {
+                    continue;
+                }
+                if let Some(cell) = screen.cell_mut(column, row) {
+                    cell.background = Some(blend(color, cell.background));
+                    cell.character = ' ';
+                }
+            }
+        }
+    }
+
+    fn draw_circle(&mut self, center: Vec2, _radius: f32, color: Color) {
+        let mut screen = self.screen.borrow_mut();
+        let (column, row) = ((center.x / CELL_SIZE.x).floor() as i32, (center.y / CELL_SIZE.y).floor() as i32);
+        if let Some(cell) = screen.cell_mut(column, row) {
+            cell.character = '●';
+            cell.foreground = Some(rgb(color));
+        }
+    }
+
+    // Text sits on its baseline, so it takes the row of cells above `position`.
+    fn draw_text(&mut self, text: &str, position: Vec2, _font_size: f32, color: Color) {
+        let mut screen = self.screen.borrow_mut();
+        let row = ((position.y - CELL_SIZE.y) / CELL_SIZE.y).round() as i32;
+        let column = (position.x / CELL_SIZE.x).round() as i32;
+        for (offset, character) in text.chars().enumerate() {
+            if let Some(cell) = screen.cell_mut(column + offset as i32, row) {
+                cell.character = character;
+                cell.foreground = Some(rgb(color));
+            }
+        }
+    }
+
+    fn measure_text(&mut self, text: &str, _font_size: u16) -> TextDimensions {
+        TextDimensions{
+            width: text.chars().count() as f32 * CELL_SIZE.x,
+            height: CELL_SIZE.y,
+            offset_y: CELL_SIZE.y,
+        }
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) {
+        self.screen.borrow_mut().clip = clip;
+    }
+
+    fn screen_size(&mut self) -> Option<Vec2> {
+        let screen = self.screen.borrow();
+        Some(vec2(screen.columns as f32, screen.rows as f32) * CELL_SIZE)
+    }
+}
+
+#[derive(Default)]
+struct InputState {
+    mouse: Option<RawPointer>,
+    keys: KeyInput,
+}
+
+///The mouse and keyboard of a terminal, as an `InputSource`.
+///
+///Clones share the same input, so every menu attached to a `Terminal` sees the same frame.
+#[derive(Clone, Default)]
+pub struct TerminalInput {
+    state: Rc<RefCell<InputState>>,
+}
+impl TerminalInput {
+    pub fn new() -> TerminalInput {
+        TerminalInput::default()
+    }
+
+    ///Start a new frame, forgetting the presses, releases and keys of the last one.
+    pub fn begin_frame(&self) {
+        let mut state = self.state.borrow_mut();
+        if let Some(mouse) = &mut state.mouse {
+            mouse.pressed = false;
+            mouse.released = false;
+            mouse.right = ButtonState{ down: mouse.right.down, ..ButtonState::default() };
+            mouse.middle = ButtonState{ down: mouse.middle.down, ..ButtonState::default() };
+        }
+        state.keys = KeyInput::default();
+    }
+
+    ///Add a terminal event to the current frame.
+    pub fn handle_event(&self, event: &Event) {
+        let mut state = self.state.borrow_mut();
+        match event {
+            Event::Mouse(mouse_event) => {
+                let mouse = state.mouse.get_or_insert(RawPointer::new(MOUSE_ID, PointerKind::Mouse, Vec2::ZERO));
+                mouse.position = cell_center(mouse_event.column as i32, mouse_event.row as i32);
+                match mouse_event.kind {
+                    MouseEventKind::Down(TermButton::Left) => {
+                        mouse.pressed = true;
+                        mouse.down = true;
+                    }
+                    MouseEventKind::Up(TermButton::Left) => {
+                        mouse.released = mouse.down;
+                        mouse.down = false;
+                    }
+                    MouseEventKind::Down(TermButton::Right) => mouse.right = ButtonState{ down: true, pressed: true, released: false },
+                    MouseEventKind::Down(TermButton::Middle) => mouse.middle = ButtonState{ down: true, pressed: true, released: false },
+                    MouseEventKind::Up(TermButton::Right) => mouse.right = ButtonState{ down: false, pressed: mouse.right.pressed, released: mouse.right.down },
+                    MouseEventKind::Up(TermButton::Middle) => mouse.middle = ButtonState{ down: false, pressed: mouse.middle.pressed, released: mouse.middle.down },
+                    _ => {}
+                }
+            }
+            Event::Key(key_event) if key_event.kind != KeyEventKind::Release => {
+                if let TermKey::Char(character) = key_event.code {
+                    if !key_event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                        state.keys.events.push(KeyEvent::Char(character));
+                    }
+                }
+                if let Some(key) = key_code(key_event.code) {
+                    state.keys.events.push(KeyEvent::Key(key));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ///Return the keys typed and pressed on the current frame.
+    pub fn keys(&self) -> KeyInput {
+        self.state.borrow().keys.clone()
+    }
+}
+impl InputSource for TerminalInput {
+    fn poll(&mut self) -> Vec<RawPointer> {
+        self.state.borrow().mouse.into_iter().collect()
+    }
+
+    fn poll_keys(&mut self) -> Option<KeyInput> {
+        Some(self.keys())
+    }
+}
+
+///Return the macroquad key for a terminal key, for the ones menus may look at.
+fn key_code(key: TermKey) -> Option<KeyCode> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+        KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M, KeyCode::N,
+        KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T, KeyCode::U,
+        KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+        KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+    ];
+
+    Some(match key {
+        TermKey::Backspace => KeyCode::Backspace,
+        TermKey::Enter => KeyCode::Enter,
+        TermKey::Esc => KeyCode::Escape,
+        TermKey::Tab | TermKey::BackTab => KeyCode::Tab,
+        TermKey::Delete => KeyCode::Delete,
+        TermKey::Left => KeyCode::Left,
+        TermKey::Right => KeyCode::Right,
+        TermKey::Up => KeyCode::Up,
+        TermKey::Down => KeyCode::Down,
+        TermKey::Home => KeyCode::Home,
+        TermKey::End => KeyCode::End,
+        TermKey::PageUp => KeyCode::PageUp,
+        TermKey::PageDown => KeyCode::PageDown,
+        TermKey::Char(' ') => KeyCode::Space,
+        TermKey::Char(character) if character.is_ascii_alphabetic() => {
+            LETTERS[(character.to_ascii_lowercase() as u8 - b'a') as usize]
+        }
+        TermKey::Char(character) if character.is_ascii_digit() => DIGITS[(character as u8 - b'0') as usize],
+        _ => return None,
+    })
+}
+
+///The terminal the program runs in, taken over to show menus until dropped.
+pub struct Terminal {
+    renderer: TerminalRenderer,
+    input: TerminalInput,
+    // Put back when the terminal is dropped.
+    previous_renderer: Option<Box<dyn Renderer>>,
+}
+impl Terminal {
+    ///Switch the terminal to an alternate screen reporting the mouse, and draw the menus of this
+    ///thread into it. Start it before building the menus.
+    pub fn start() -> std::io::Result<Terminal> {
+        let (columns, rows) = terminal::size()?;
+        terminal::enable_raw_mode()?;
+        let mut out = std::io::stdout();
+        out.queue(terminal::EnterAlternateScreen)?;
+        out.queue(event::EnableMouseCapture)?;
+        out.queue(cursor::Hide)?;
+        out.flush()?;
+
+        let renderer = TerminalRenderer::new(columns, rows);
+        Ok(Terminal{
+            previous_renderer: Some(set_renderer(renderer.clone())),
+            renderer,
+            input: TerminalInput::new(),
+        })
+    }
+
+    ///Make `menu` read the terminal's mouse and keyboard, and animate with the time passed between updates.
+    pub fn attach(&self, menu: &mut Menu) {
+        menu.set_input(self.input.clone());
+        let mut last_update = Instant::now();
+        menu.animator.set_clock(move || {
+            let now = Instant::now();
+            let delta = (now - last_update).as_secs_f32();
+            last_update = now;
+            delta
+        });
+    }
+
+    ///Read the events that came since the last call, for the menus to update with, and return the keys among them.
+    pub fn read_input(&mut self) -> std::io::Result<KeyInput> {
+        self.input.begin_frame();
+        while event::poll(Duration::ZERO)? {
+            let event = event::read()?;
+            if let Event::Resize(columns, rows) = event {
+                self.renderer.resize(columns, rows);
+            }
+            self.input.handle_event(&event);
+        }
+
+        Ok(self.input.keys())
+    }
+
+    ///Empty the screen before drawing the menus.
+    pub fn clear(&self) {
+        self.renderer.clear();
+    }
+
+    ///Show what was drawn since **clear**.
+    pub fn present(&mut self) -> std::io::Result<()> {
+        self.renderer.present(&mut std::io::stdout())
+    }
+}
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Leave the terminal as it was, even when the program is exiting with an error.
+        let mut out = std::io::stdout();
+        let _ = out.queue(cursor::Show);
+        let _ = out.queue(event::DisableMouseCapture);
+        let _ = out.queue(terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+        if let Some(renderer) = self.previous_renderer.take() {
+            replace_renderer(renderer);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Drive menus without a window, to test them with a plain `cargo test`.
+//!
+//! **headless** swaps macroquad out for a renderer that draws nothing and measures text with
+//! fixed metrics, and **attach** feeds a menu from a `TestInput` instead of the mouse.
+//! **menu_with_input** does both for a new menu:
+//!
+//! ```no_run
+//! use Simples_menu::{Button, PositionType};
+//! use Simples_menu::macroquad::prelude::*;
+//! use Simples_menu::testing;
+//!
+//! let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+//! let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+//!
+//! input.move_mouse(menu.element_rect(&button).unwrap().center());
+//! input.press();
+//! menu.update();
+//! assert!(button.read().has_been_pressed);
+//! ```
+//!
+//! What a menu draws can be compared against a golden file with **assert_snapshot**, to catch
+//! layout changes without rendering any pixels.
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use macroquad::prelude::*;
+use macroquad::text::TextDimensions;
+use crate::Menu;
+use crate::animation::FixedClock;
+use crate::input::{ButtonState, InputSource, KeyEvent, KeyInput, PointerKind, RawPointer, MOUSE_ID};
+use crate::render::{record, set_renderer, DisplayList, Renderer};
+
+///The time step of the menus given to **attach**, in seconds.
+pub const FRAME_TIME: f32 = 1.0 / 60.0;
+///The size of the screen a `MockRenderer` reports.
+pub const SCREEN_SIZE: Vec2 = Vec2{ x: 800.0, y: 600.0 };
+
+///A renderer that draws nothing, for tests.
+///
+///Every character is measured half of the font size wide, and text three quarters of it high,
+///on a screen of `SCREEN_SIZE`, so layouts come out the same on every machine.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MockRenderer;
+impl Renderer for MockRenderer {
+    fn draw_rectangle(&mut self, _rect: Rect, _color: Color) {}
+
+    fn draw_circle(&mut self, _center: Vec2, _radius: f32, _color: Color) {}
+
+    fn draw_text(&mut self, _text: &str, _position: Vec2, _font_size: f32, _color: Color) {}
+
+    fn measure_text(&mut self, text: &str, font_size: u16) -> TextDimensions {
+        let height = font_size as f32 * 0.75;
+        TextDimensions{
+            width: text.chars().count() as f32 * font_size as f32 / 2.0,
+            height,
+            offset_y: height,
+        }
+    }
+
+    fn set_clip(&mut self, _clip: Option<Rect>) {}
+
+    fn screen_size(&mut self) -> Option<Vec2> {
+        Some(SCREEN_SIZE)
+    }
+}
+
+///Draw and measure with a `MockRenderer` on this thread, before building the menus of a test.
+pub fn headless() {
+    set_renderer(MockRenderer);
+}
+
+#[derive(Default)]
+struct InputState {
+    pointers: Vec<RawPointer>,
+    keys: KeyInput,
+}
+
+///Pointers and keys set by a test, read by a menu on its next **update**.
+///
+///Clones share the same input, so one can be given to the menu and the other kept to drive it.
+///Presses and releases last for one update, while pointers stay down until released.
+#[derive(Clone)]
+pub struct TestInput {
+    state: Rc<RefCell<InputState>>,
+}
+impl TestInput {
+    ///Create an input with the mouse up at the top left corner of the screen.
+    pub fn new() -> TestInput {
+        let input = TestInput{
+            state: Rc::new(RefCell::new(InputState::default())),
+        };
+        input.set_pointer(MOUSE_ID, PointerKind::Mouse, Vec2::ZERO, |_| {});
+        input
+    }
+
+    fn set_pointer(&self, id: u64, kind: PointerKind, position: Vec2, change: impl FnOnce(&mut RawPointer)) {
+        let mut state = self.state.borrow_mut();
+        let index = match state.pointers.iter().position(|pointer| pointer.id == id) {
+            Some(index) => index,
+            None => {
+                state.pointers.push(RawPointer::new(id, kind, position));
+                state.pointers.len() - 1
+            }
+        };
+        let pointer = &mut state.pointers[index];
+        pointer.position = position;
+        change(pointer);
+    }
+
+    fn mouse_position(&self) -> Vec2 {
+        self.state.borrow().pointers.iter()
+            .find(|pointer| pointer.id == MOUSE_ID)
+            .map_or(Vec2::ZERO, |pointer| pointer.position)
+    }
+
+    ///Move the mouse to `position` on the screen.
+    pub fn move_mouse(&self, position: Vec2) {
+        self.set_pointer(MOUSE_ID, PointerKind::Mouse, position, |_| {});
+    }
+
+    ///Press the left mouse button where the mouse is.
+    pub fn press(&self) {
+        self.set_pointer(MOUSE_ID, PointerKind::Mouse, self.mouse_position(), |pointer| {
+            pointer.pressed = !pointer.down;
+            pointer.down = true;
+        });
+    }
+
+    ///Release the left mouse button.
+    pub fn release(&self) {
+        self.set_pointer(MOUSE_ID, PointerKind::Mouse, self.mouse_position(), |pointer| {
+            pointer.released = pointer.down;
+            pointer.down = false;
+        });
+    }
+
+    ///Press `button` where the mouse is.
+    pub fn press_button(&self, button: MouseButton) {
+        self.set_pointer(MOUSE_ID, PointerKind::Mouse, self.mouse_position(), |pointer| {
+            let (down, pressed) = match button {
+                MouseButton::Right => (&mut pointer.right.down, &mut pointer.right.pressed),
+                MouseButton::Middle => (&mut pointer.middle.down, &mut pointer.middle.pressed),
+                _ => (&mut pointer.down, &mut pointer.pressed),
+            };
+            *pressed = !*down;
+            *down = true;
+        });
+    }
+
+    ///Release `button`.
+    pub fn release_button(&self, button: MouseButton) {
+        self.set_pointer(MOUSE_ID, PointerKind::Mouse, self.mouse_position(), |pointer| {
+            let (down, released) = match button {
+                MouseButton::Right => (&mut pointer.right.down, &mut pointer.right.released),
+                MouseButton::Middle => (&mut pointer.middle.down, &mut pointer.middle.released),
+                _ => (&mut pointer.down, &mut pointer.released),
+            };
+            *released = *down;
+            *down = false;
+        });
+    }
+
+    ///Put the finger `id` down at `position` on the screen.
+    pub fn touch_down(&self, id: u64, position: Vec2) {
+        self.set_pointer(id, PointerKind::Touch, position, |pointer| {
+            pointer.pressed = true;
+            pointer.down = true;
+        });
+    }
+
+    ///Move the finger `id`, which is down, to `position` on the screen.
+    pub fn touch_move(&self, id: u64, position: Vec2) {
+        self.set_pointer(id, PointerKind::Touch, position, |_| {});
+    }
+
+    ///Lift the finger `id`.
+    pub fn touch_up(&self, id: u64) {
+        let position = self.state.borrow().pointers.iter()
+            .find(|pointer| pointer.id == id)
+            .map_or(Vec2::ZERO, |pointer| pointer.position);
+        self.set_pointer(id, PointerKind::Touch, position, |pointer| {
+            pointer.released = pointer.down;
+            pointer.down = false;
+        });
+    }
+
+    ///Type `text` on the keyboard.
+    pub fn type_text(&self, text: &str) {
+        self.state.borrow_mut().keys.events.extend(text.chars().map(KeyEvent::Char));
+    }
+
+    ///Press `key` on the keyboard.
+    pub fn press_key(&self, key: KeyCode) {
+        self.state.borrow_mut().keys.events.push(KeyEvent::Key(key));
+    }
+}
+impl Default for TestInput {
+    fn default() -> Self {
+        TestInput::new()
+    }
+}
+impl InputSource for TestInput {
+    fn poll(&mut self) -> Vec<RawPointer> {
+        let mut state = self.state.borrow_mut();
+        let pointers = state.pointers.clone();
+
+        // Presses and releases only last a frame, and lifted fingers go away.
+        state.pointers.retain(|pointer| pointer.kind == PointerKind::Mouse || pointer.down);
+        for pointer in &mut state.pointers {
+            pointer.pressed = false;
+            pointer.released = false;
+            pointer.right = ButtonState{ down: pointer.right.down, ..ButtonState::default() };
+            pointer.middle = ButtonState{ down: pointer.middle.down, ..ButtonState::default() };
+        }
+
+        pointers
+    }
+
+    fn poll_keys(&mut self) -> Option<KeyInput> {
+        Some(std::mem::take(&mut self.state.borrow_mut().keys))
+    }
+}
+
+///Make `menu` read its input from the returned `TestInput`, and advance by `FRAME_TIME` on every update.
+pub fn attach(menu: &mut Menu) -> TestInput {
+    let input = TestInput::new();
+    menu.set_input(input.clone());
+    menu.animator.set_clock(FixedClock(FRAME_TIME));
+
+    input
+}
+
+///Build an empty menu drawn and measured by a `MockRenderer`, see **headless**.
+pub fn menu(title: &str, position: Vec2) -> Menu {
+    headless();
+    Menu::new(title.to_string(), position)
+}
+
+///Build an empty menu like **menu**, reading its input from the returned `TestInput`, see **attach**.
+pub fn menu_with_input(title: &str, position: Vec2) -> (Menu, TestInput) {
+    let mut menu = menu(title, position);
+    let input = attach(&mut menu);
+    (menu, input)
+}
+
+///Record everything `menu` draws, see `render::record`.
+pub fn snapshot(menu: &Menu) -> DisplayList {
+    record(|| menu.draw())
+}
+
+///Return where the golden file of the snapshot `name` is kept, `tests/snapshots/<name>.snap` in the package being tested.
+pub fn snapshot_path(name: &str) -> PathBuf {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from);
+    root.join("tests").join("snapshots").join(format!("{name}.snap"))
+}
+
+///Compare `list` against the golden file of the snapshot `name`, panicking on the first line that differs.
+///
+///Run the tests with `UPDATE_SNAPSHOTS=1` to write the golden files from what is drawn instead,
+///after checking the changes are the ones expected.
+pub fn assert_snapshot(name: &str, list: &DisplayList) {
+    let path = snapshot_path(name);
+    let actual = list.to_string();
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().expect("the path is in tests/snapshots")).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!("no snapshot at {}, run the tests with UPDATE_SNAPSHOTS=1 to write it", path.display());
+    };
+    // Golden files checked out on Windows may have their line endings changed.
+    let expected = expected.replace("\r\n", "\n");
+    if expected == actual {
+        return;
+    }
+
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected), Some(actual)) if expected == actual => continue,
+            (expected, actual) => panic!(
+                "snapshot {name} differs from {} on line {line}\n  expected: {}\n  actual:   {}\nrun the tests with UPDATE_SNAPSHOTS=1 to accept the change",
+                path.display(),
+                expected.unwrap_or("<end>"),
+                actual.unwrap_or("<end>"),
+            ),
+        }
+    }
+}
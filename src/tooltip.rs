@@ -0,0 +1,117 @@
+//! Tooltips shown while the pointer rests on an element.
+//!
+//! Any element can be given a tooltip with **Element::set_tooltip**, a line of text or a whole
+//! widget. Once the pointer has rested on the element for **delay**, its menu queues the tooltip
+//! when drawn, and **draw_overlay** draws it next to the pointer, kept on the screen. Draw it
+//! after every menu, so it shows above all of them:
+//!
+//! ```ignore
+//! save.set_tooltip("Save the game");
+//! loop {
+//!     menu.update();
+//!     menu.draw();
+//!     Simples_menu::tooltip::draw_overlay();
+//!     next_frame().await
+//! }
+//! ```
+//!
+//! Clicking anywhere hides the tooltip until the pointer moves to another element.
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use macroquad::prelude::*;
+use crate::render::{draw_rectangle, draw_text, from_screen, measure_text, screen_size};
+use crate::MenuElement;
+
+const FONT_SIZE: f32 = 13.0;
+const PADDING: f32 = 4.0;
+// Where the tooltip goes from the pointer, below it and to the right, clear of the cursor.
+const OFFSET: Vec2 = Vec2{ x: 12.0, y: 16.0 };
+
+///What is shown in a tooltip.
+pub enum Tooltip {
+    Text(String),
+    ///Any element, drawn with the top left corner of its bounding rectangle next to the pointer.
+    Widget(Box<dyn MenuElement>),
+}
+impl From<&str> for Tooltip {
+    fn from(text: &str) -> Tooltip {
+        Tooltip::Text(text.to_string())
+    }
+}
+impl From<String> for Tooltip {
+    fn from(text: String) -> Tooltip {
+        Tooltip::Text(text)
+    }
+}
+impl Tooltip {
+    fn size(&self) -> Vec2 {
+        match self {
+            Tooltip::Text(text) => {
+                let text_size = measure_text(text, FONT_SIZE as u16);
+                vec2(text_size.width, text_size.height) + Vec2::splat(PADDING * 2.0)
+            }
+            Tooltip::Widget(widget) => widget.bounding_rect().map_or(Vec2::ZERO, |rect| rect.size()),
+        }
+    }
+
+    fn draw(&self, position: Vec2) {
+        match self {
+            Tooltip::Text(text) => {
+                let size = self.size();
+                draw_rectangle(position.x, position.y, size.x, size.y, Color{ r: 0.1, g: 0.1, b: 0.1, a: 0.9 });
+                draw_text(text, position.x + PADDING, position.y + size.y - PADDING, FONT_SIZE, WHITE);
+            }
+            Tooltip::Widget(widget) => {
+                let corner = widget.bounding_rect().map_or(Vec2::ZERO, |rect| rect.point());
+                widget.draw(position - corner);
+            }
+        }
+    }
+}
+
+// A tooltip shared with the element it belongs to, or made up by its menu.
+pub(crate) type SharedTooltip = Rc<RefCell<Option<Tooltip>>>;
+
+thread_local! {
+    static DELAY: Cell<f32> = const { Cell::new(0.5) };
+    // The tooltip to draw on the next **draw_overlay**, with the pointer position on the screen.
+    static QUEUED: RefCell<Option<(Vec2, SharedTooltip)>> = const { RefCell::new(None) };
+}
+
+///Set how long, in seconds, the pointer has to rest on an element before its tooltip shows, for every menu.
+pub fn set_delay(seconds: f32) {
+    DELAY.with(|current| current.set(seconds));
+}
+
+///Return how long the pointer has to rest on an element before its tooltip shows. Defaults to half a second.
+pub fn delay() -> f32 {
+    DELAY.with(|current| current.get())
+}
+
+///Show `tooltip` next to `pointer`, on the screen, on the next **draw_overlay**, instead of any queued before.
+pub(crate) fn queue(pointer: Vec2, tooltip: SharedTooltip) {
+    QUEUED.with(|queued| *queued.borrow_mut() = Some((pointer, tooltip)));
+}
+
+///Drop the queued tooltip, for a menu drawn over the element it belongs to.
+pub(crate) fn clear() {
+    QUEUED.with(|queued| *queued.borrow_mut() = None);
+}
+
+///Draw the tooltip queued by the menus drawn since the last call, if any, above everything drawn before.
+pub fn draw_overlay() {
+    let Some((pointer, tooltip)) = QUEUED.with(|queued| queued.borrow_mut().take()) else {
+        return;
+    };
+    let tooltip = tooltip.borrow();
+    let Some(tooltip) = tooltip.as_ref() else {
+        return;
+    };
+
+    let size = tooltip.size();
+    let mut position = from_screen(pointer) + OFFSET;
+    if let Some(screen) = screen_size().map(from_screen) {
+        position = position.min(screen - size).max(Vec2::ZERO);
+    }
+    tooltip.draw(position);
+}
@@ -0,0 +1,69 @@
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::animation::{Easing, Lerp, Tween};
+
+const EASINGS: [Easing; 9] = [
+    Easing::Linear,
+    Easing::QuadIn,
+    Easing::QuadOut,
+    Easing::QuadInOut,
+    Easing::CubicIn,
+    Easing::CubicOut,
+    Easing::CubicInOut,
+    Easing::SineInOut,
+    Easing::BackOut,
+];
+
+fn assert_near(value: f32, expected: f32) {
+    assert!((value - expected).abs() < 1e-5, "{value} isn't {expected}");
+}
+
+#[test]
+fn every_easing_starts_at_zero_and_ends_at_one() {
+    for easing in EASINGS {
+        assert_near(easing.apply(0.0), 0.0);
+        assert_near(easing.apply(1.0), 1.0);
+        // Progress outside of the tween is clamped.
+        assert_near(easing.apply(-1.0), 0.0);
+        assert_near(easing.apply(2.0), 1.0);
+    }
+}
+
+#[test]
+fn easings_follow_their_curves() {
+    assert_near(Easing::Linear.apply(0.25), 0.25);
+    assert_near(Easing::QuadIn.apply(0.5), 0.25);
+    assert_near(Easing::QuadOut.apply(0.5), 0.75);
+    assert_near(Easing::CubicIn.apply(0.5), 0.125);
+    for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut] {
+        assert_near(easing.apply(0.5), 0.5);
+    }
+    // Overshoots the end before settling on it.
+    assert!(Easing::BackOut.apply(0.8) > 1.0);
+}
+
+#[test]
+fn tweens_step_towards_their_target() {
+    let mut tween = Tween::new(10.0, 20.0, 2.0, Easing::Linear);
+    assert_eq!(tween.value(), 10.0);
+    assert_eq!(tween.step(0.5), 12.5);
+    assert_eq!(tween.progress(), 0.25);
+    assert!(!tween.is_finished());
+
+    // Stepping past the end stops on the target.
+    assert_eq!(tween.step(5.0), 20.0);
+    assert_eq!(tween.progress(), 1.0);
+    assert!(tween.is_finished());
+}
+
+#[test]
+fn tweens_without_a_duration_are_already_finished() {
+    let tween = Tween::new(vec2(0.0, 0.0), vec2(4.0, 8.0), 0.0, Easing::QuadIn);
+    assert!(tween.is_finished());
+    assert_eq!(tween.value(), vec2(4.0, 8.0));
+}
+
+#[test]
+fn colors_are_interpolated_per_channel() {
+    let color = Color::new(0.0, 1.0, 0.5, 0.0).lerp(Color::new(1.0, 0.0, 0.5, 1.0), 0.25);
+    assert_eq!(color, Color::new(0.25, 0.75, 0.5, 0.25));
+}
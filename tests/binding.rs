@@ -0,0 +1,69 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::binding::{Binding, Lens};
+use Simples_menu::testing;
+use Simples_menu::{CheckBox, PositionType, Slider};
+
+struct Settings {
+    volume: f32,
+}
+
+#[test]
+fn shared_cells_and_lenses_read_and_write_the_state() {
+    let cell = Rc::new(Cell::new(3));
+    cell.set(4);
+    assert_eq!(Binding::get(&cell), 4);
+
+    let settings = Rc::new(RefCell::new(Settings{ volume: 0.5 }));
+    let volume = Lens::new(settings.clone(), |settings| settings.volume, |settings, volume| settings.volume = volume);
+    assert_eq!(volume.get(), 0.5);
+    volume.set(0.25);
+    assert_eq!(settings.borrow().volume, 0.25);
+}
+
+#[test]
+fn elements_pick_up_changes_made_by_the_game() {
+    let (mut menu, _input) = testing::menu_with_input("Menu", Vec2::ZERO);
+    let checked = Rc::new(Cell::new(true));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    check_box.edit().bind(checked.clone());
+    assert!(check_box.read().is_checked);
+
+    checked.set(false);
+    menu.update();
+    assert!(!check_box.read().is_checked);
+}
+
+#[test]
+fn elements_write_user_changes_back() {
+    let (mut menu, input) = testing::menu_with_input("Menu", Vec2::ZERO);
+    let checked = Rc::new(Cell::new(false));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    check_box.edit().bind(checked.clone());
+
+    input.move_mouse(menu.element_rect(&check_box).unwrap().center());
+    input.press();
+    menu.update();
+    input.release();
+    menu.update();
+    assert!(checked.get());
+}
+
+#[test]
+fn a_bound_slider_keeps_its_value_in_range() {
+    let (mut menu, _input) = testing::menu_with_input("Menu", Vec2::ZERO);
+    let volume = Rc::new(Cell::new(0.5));
+    let slider = menu.add_element(Slider::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0), 0.0, 1.0, 0.0));
+    slider.edit().bind(volume.clone());
+
+    volume.set(3.0);
+    menu.update();
+    assert_eq!(slider.read().value, 1.0);
+    assert_eq!(volume.get(), 1.0);
+
+    volume.set(-1.0);
+    menu.update();
+    assert_eq!(slider.read().value, 0.0);
+    assert_eq!(volume.get(), 0.0);
+}
@@ -0,0 +1,39 @@
+use Simples_menu::i18n::{self, StringTable};
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::Title;
+
+fn title(name: &str, key: &str) -> Title {
+    Title{
+        name: name.to_string(),
+        color: WHITE,
+        font_size: 20.0,
+        key: Some(key.to_string()),
+    }
+}
+
+#[test]
+fn tables_are_read_from_key_value_lines() {
+    let table = StringTable::parse("# Main menu\n\nmenu.start = Start\nmenu.help = Line one\\nLine two\n").unwrap();
+    assert_eq!(table.get("menu.start"), Some("Start"));
+    assert_eq!(table.get("menu.help"), Some("Line one\nLine two"));
+    assert_eq!(table.get("# Main menu"), None);
+
+    let error = StringTable::parse("menu.start = Start\nmenu.quit\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 2: expected `key = value`");
+}
+
+#[test]
+fn missing_translations_fall_back_to_the_default_language_then_the_name() {
+    i18n::add_language("en", StringTable::parse("menu.start = Start\nmenu.quit = Quit").unwrap());
+    i18n::add_language("pt", StringTable::parse("menu.start = Iniciar").unwrap());
+    i18n::set_language("pt");
+
+    assert_eq!(title("start", "menu.start").text(), "Iniciar");
+    assert_eq!(title("quit", "menu.quit").text(), "Quit");
+    assert_eq!(title("Options", "menu.options").text(), "Options");
+
+    i18n::set_default_language("pt");
+    i18n::set_language("fr");
+    assert_eq!(i18n::translate("menu.start").as_deref(), Some("Iniciar"));
+    assert_eq!(i18n::translate("menu.quit"), None);
+}
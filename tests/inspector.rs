@@ -0,0 +1,222 @@
+use Simples_menu::inspector::Inspector;
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::testing::{self, TestInput};
+use Simples_menu::{Button, CheckBox, Element, Menu, MenuElement, PositionType, Slider};
+
+struct Scene {
+    settings: Menu,
+    apply: Element<Button>,
+    audio: Element<Menu>,
+    _mute: Element<CheckBox>,
+}
+
+fn scene() -> Scene {
+    let mut settings = testing::menu("Settings", vec2(50.0, 50.0));
+    let apply = settings.add_named_element("apply", Button::new("Apply".to_string(), PositionType::TopLeft, vec2(0.0, 0.0), None));
+    let audio = settings.add_named_element("audio", Menu::new("Audio".to_string(), vec2(0.0, 60.0)));
+    let mute = audio.edit().add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    Scene{ settings, apply, audio, _mute: mute }
+}
+
+fn inspector() -> (Inspector, TestInput) {
+    let mut inspector = Inspector::new(vec2(400.0, 20.0));
+    let input = testing::attach(&mut inspector.menu);
+    (inspector, input)
+}
+
+fn row_titles(inspector: &Inspector) -> Vec<String> {
+    inspector.menu.elements.iter()
+        .filter(|element| element.type_name().ends_with("Button"))
+        .filter_map(|element| element.read().properties().title)
+        .collect()
+}
+
+fn click(inspector: &mut Inspector, input: &TestInput, element: &Element<dyn MenuElement>, menus: &mut [&mut Menu]) {
+    input.move_mouse(inspector.menu.element_rect(element).unwrap().center());
+    input.press();
+    inspector.update(menus);
+    input.release();
+    inspector.update(menus);
+}
+
+#[test]
+fn lists_menus_and_nested_elements() {
+    let mut scene = scene();
+    let (mut inspector, _input) = inspector();
+    inspector.update(&mut [&mut scene.settings]);
+
+    let rows = row_titles(&inspector);
+    for row in ["Menu Settings", "  #0 Button apply", "  #1 Menu audio", "    #0 CheckBox"] {
+        assert!(rows.iter().any(|title| title == row), "no row {row:?} in {rows:?}");
+    }
+}
+
+#[test]
+fn clicking_a_row_selects_it() {
+    let mut scene = scene();
+    let (mut inspector, input) = inspector();
+    inspector.update(&mut [&mut scene.settings]);
+
+    let row = inspector.menu.elements.iter()
+        .find(|element| element.read().properties().title.as_deref() == Some("  #1 Menu audio"))
+        .cloned()
+        .unwrap();
+    click(&mut inspector, &input, &row, &mut [&mut scene.settings]);
+
+    assert_eq!(inspector.selected(), Some(&[0, 1][..]));
+    assert_eq!(inspector.properties().unwrap().title.as_deref(), Some("Audio"));
+}
+
+#[test]
+fn typing_in_a_field_edits_the_selection() {
+    let mut scene = scene();
+    let (mut inspector, input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 0]);
+    inspector.update(&mut [&mut scene.settings]);
+
+    // The first text input holds the x position.
+    let x_field = inspector.menu.elements.iter()
+        .find(|element| element.type_name().ends_with("TextInput"))
+        .cloned()
+        .unwrap();
+    click(&mut inspector, &input, &x_field, &mut [&mut scene.settings]);
+    input.press_key(KeyCode::Backspace);
+    inspector.update(&mut [&mut scene.settings]);
+    input.type_text("42");
+    inspector.update(&mut [&mut scene.settings]);
+
+    assert_eq!(scene.apply.read().position, vec2(42.0, 0.0));
+}
+
+#[test]
+fn menus_without_a_size_show_the_one_they_fit() {
+    let mut scene = scene();
+    let (mut inspector, input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0]);
+    inspector.update(&mut [&mut scene.settings]);
+    let fitted = inspector.properties().unwrap().size.unwrap();
+    assert!(fitted.x > 0.0 && fitted.y > 0.0);
+
+    // The text inputs hold the x and y position, then the width and height.
+    let fields: Vec<_> = inspector.menu.elements.iter()
+        .filter(|element| element.type_name().ends_with("TextInput"))
+        .cloned()
+        .collect();
+    let type_in = |inspector: &mut Inspector, menu: &mut Menu, field: &Element<dyn MenuElement>, text: &str| {
+        click(inspector, &input, field, &mut [menu]);
+        for _ in 0..10 {
+            input.press_key(KeyCode::Backspace);
+            inspector.update(&mut [menu]);
+        }
+        input.type_text(text);
+        inspector.update(&mut [menu]);
+    };
+
+    // Moving the menu keeps it fitted to its elements.
+    type_in(&mut inspector, &mut scene.settings, &fields[0], "60");
+    assert_eq!(scene.settings.position.x, 60.0);
+    assert_eq!(scene.settings.size, None);
+
+    type_in(&mut inspector, &mut scene.settings, &fields[2], "300");
+    assert_eq!(scene.settings.size, Some(vec2(300.0, fitted.y)));
+}
+
+#[test]
+fn follows_changes_made_by_the_game() {
+    let mut scene = scene();
+    let (mut inspector, _input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 1]);
+    inspector.update(&mut [&mut scene.settings]);
+
+    scene.audio.edit().visible = false;
+    inspector.update(&mut [&mut scene.settings]);
+
+    assert_eq!(inspector.properties().unwrap().visible, Some(false));
+    assert!(scene.audio.read().bounding_rect().is_none());
+}
+
+#[test]
+fn snippets_hold_the_edited_values() {
+    let mut scene = scene();
+    scene.apply.edit().color = Color::new(0.5, 0.25, 1.0, 1.0);
+    let (mut inspector, _input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 0]);
+    inspector.update(&mut [&mut scene.settings]);
+
+    let size = scene.apply.read().size;
+    assert_eq!(inspector.rust_snippet().unwrap(), format!(
+        "apply.edit().position = vec2(0.0, 0.0);\n\
+         apply.edit().size = vec2({:?}, {:?});\n\
+         apply.edit().color = Color::new(0.5, 0.25, 1.0, 1.0);\n\
+         apply.edit().title.name = \"Apply\".to_string();\n\
+         apply.edit().visible = true;\n\
+         apply.edit().enabled = true;",
+        size.x, size.y,
+    ));
+    assert_eq!(inspector.ron_snippet().unwrap(), format!(
+        "Button(name: \"apply\", label: \"Apply\", position: (0.0, 0.0), size: ({:?}, {:?}), color: (0.5, 0.25, 1.0, 1.0), visible: true, enabled: true)",
+        size.x, size.y,
+    ));
+
+    inspector.select(&mut [&mut scene.settings], vec![0]);
+    inspector.update(&mut [&mut scene.settings]);
+    // A menu without a size is written with the one it fits its elements with.
+    let size = inspector.properties().unwrap().size.unwrap();
+    assert_eq!(inspector.ron_snippet().unwrap(), format!(
+        "Menu(title: \"Settings\", position: (50.0, 50.0), size: ({:?}, {:?}), color: (0.31, 0.31, 0.31, 1.0), visible: true)",
+        size.x, size.y,
+    ));
+}
+
+fn field(inspector: &Inspector, title: &str) -> Element<dyn MenuElement> {
+    inspector.menu.elements.iter()
+        .find(|element| element.read().properties().title.as_deref() == Some(title))
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn snippet_buttons_hand_the_snippet_to_the_game() {
+    let mut scene = scene();
+    let (mut inspector, input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 0]);
+    inspector.update(&mut [&mut scene.settings]);
+    assert_eq!(inspector.snippet(), None);
+
+    let ron = field(&inspector, "RON");
+    click(&mut inspector, &input, &ron, &mut [&mut scene.settings]);
+    assert_eq!(inspector.snippet(), inspector.ron_snippet().as_deref());
+    inspector.update(&mut [&mut scene.settings]);
+    assert_eq!(inspector.snippet(), None);
+
+    let rust = field(&inspector, "Rust");
+    click(&mut inspector, &input, &rust, &mut [&mut scene.settings]);
+    assert_eq!(inspector.snippet(), inspector.rust_snippet().as_deref());
+}
+
+#[test]
+fn only_types_the_loader_builds_are_written_as_ron() {
+    let mut scene = scene();
+    let _volume = scene.settings.add_element(Slider::new(PositionType::TopLeft, vec2(0.0, 200.0), vec2(100.0, 20.0), 0.0, 1.0, 0.5));
+    let (mut inspector, _input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 2]);
+    inspector.update(&mut [&mut scene.settings]);
+
+    assert!(inspector.rust_snippet().is_some());
+    assert_eq!(inspector.ron_snippet(), None);
+    assert!(!field(&inspector, "RON").read().properties().visible.unwrap());
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn ron_snippet_loads_back() {
+    let mut scene = scene();
+    let (mut inspector, _input) = inspector();
+    inspector.select(&mut [&mut scene.settings], vec![0, 0]);
+    inspector.update(&mut [&mut scene.settings]);
+
+    let source = format!("Menu(title: \"Loaded\", position: (0.0, 0.0), elements: [{}])", inspector.ron_snippet().unwrap());
+    let layout = Simples_menu::loader::load_ron(&source).unwrap();
+    let button = layout.button("apply").unwrap();
+    assert_eq!(button.read().properties(), scene.apply.read().properties());
+}
@@ -0,0 +1,553 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::animation::{Easing, Transition};
+use Simples_menu::input::{self, DoubleClickThreshold, EventKind};
+use Simples_menu::render::{self, DrawCommand};
+use Simples_menu::testing::{self, FRAME_TIME};
+use Simples_menu::{Activation, Button, CheckBox, Clickable, Dropdown, Interactive, Menu, MenuElement, PositionType, Slider, SmartButton, TextInput};
+
+#[test]
+fn button_is_pressed_under_the_mouse() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+
+    input.press();
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+    assert!(button.read().is_pressed);
+
+    input.release();
+    menu.update();
+    assert!(button.read().has_been_pressed);
+    assert!(!button.read().is_pressed);
+
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn dragging_off_a_button_cancels_the_click() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let other = menu.add_element(Button::new("Quit".to_string(), PositionType::TopLeft, vec2(0.0, 40.0), None));
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    input.press();
+    menu.update();
+
+    // The button keeps the pointer while it is held, so the other one isn't pressed or clicked.
+    input.move_mouse(menu.element_rect(&other).unwrap().center());
+    menu.update();
+    assert!(!button.read().is_pressed);
+    assert!(!other.read().is_pressed);
+    input.release();
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+    assert!(!other.read().has_been_pressed);
+
+    // Dragged back on before letting go, it still clicks.
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    input.press();
+    menu.update();
+    input.move_mouse(vec2(0.0, 300.0));
+    menu.update();
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    menu.update();
+    assert!(button.read().is_pressed);
+    input.release();
+    menu.update();
+    assert!(button.read().has_been_pressed);
+}
+
+#[test]
+fn releasing_over_a_button_pressed_elsewhere_is_no_click() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    input.move_mouse(vec2(0.0, 300.0));
+    input.press();
+    menu.update();
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    menu.update();
+    input.release();
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn buttons_activated_on_press_click_right_away() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Fire".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    button.edit().activation = Activation::OnPress;
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    input.press();
+    menu.update();
+    assert!(button.read().has_been_pressed);
+
+    input.release();
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn smart_buttons_act_when_clicked() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(SmartButton::new("Buy".to_string(), PositionType::TopLeft, Vec2::ZERO, None, |count: Rc<Cell<u32>>| count.set(count.get() + 1)));
+    let count = Rc::new(Cell::new(0));
+    let click = |menu: &mut Menu| {
+        input.move_mouse(menu.element_rect(&button).unwrap().center());
+        input.press();
+        menu.update();
+        button.read().smart_action(count.clone());
+        let on_press = count.get();
+        input.release();
+        menu.update();
+        button.read().smart_action(count.clone());
+        (on_press, count.get())
+    };
+
+    assert_eq!(click(&mut menu), (0, 1));
+    button.edit().activation = Activation::OnPress;
+    assert_eq!(click(&mut menu), (2, 2));
+    button.edit().enabled = false;
+    assert_eq!(click(&mut menu), (2, 2));
+}
+
+#[test]
+fn pressed_buttons_are_tinted_darker_than_hovered_ones() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let button_color = |menu: &Menu| testing::snapshot(menu).commands.iter().find_map(|command| match command {
+        DrawCommand::Rectangle { rect, color } if *rect == menu.element_rect(&button).unwrap() => Some(*color),
+        _ => None,
+    }).unwrap();
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    menu.update();
+    let hovered = button_color(&menu);
+    input.press();
+    menu.update();
+    let pressed = button_color(&menu);
+
+    assert!(hovered.r < GRAY.r);
+    assert!(pressed.r < hovered.r);
+}
+
+#[test]
+fn an_open_dropdown_list_is_above_the_elements_under_it() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let options = vec!["Low".to_string(), "Medium".to_string(), "High".to_string()];
+    let dropdown = menu.add_element(Dropdown::new(PositionType::TopLeft, Vec2::ZERO, Some(vec2(80.0, 20.0)), options));
+    let button = menu.add_element(Button::new("Apply".to_string(), PositionType::TopLeft, vec2(0.0, 25.0), Some(vec2(80.0, 40.0))));
+    let click = |menu: &mut Menu, position: Vec2| {
+        input.move_mouse(position);
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+    };
+
+    let header = menu.element_rect(&dropdown).unwrap();
+    click(&mut menu, header.center());
+    assert!(dropdown.read().is_open);
+    // The open list doesn't move the elements under it.
+    assert_eq!(menu.element_rect(&dropdown), Some(header));
+    assert_eq!(menu.element_rect(&button).unwrap().y, header.y + 25.0);
+    assert_eq!(dropdown.read().overlay_rect().unwrap().h, header.h * 3.0);
+
+    // The second option lies over the button, which is added later but doesn't get the click.
+    click(&mut menu, header.point() + vec2(10.0, header.h * 2.5));
+    assert_eq!(dropdown.read().selected_option(), Some("Medium"));
+    assert!(!dropdown.read().is_open);
+    assert!(!button.read().has_been_pressed);
+    assert_eq!(menu.element_rect(&dropdown), Some(header));
+}
+
+#[test]
+fn an_open_dropdown_list_is_drawn_last() {
+    let (mut menu, _input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let dropdown = menu.add_element(Dropdown::new(PositionType::TopLeft, Vec2::ZERO, Some(vec2(80.0, 20.0)), vec!["Low".to_string(), "High".to_string()]));
+    let _button = menu.add_element(Button::new("Apply".to_string(), PositionType::TopLeft, vec2(0.0, 25.0), None));
+    dropdown.edit().is_open = true;
+    menu.update();
+
+    let texts: Vec<String> = testing::snapshot(&menu).commands.into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::Text { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts[texts.len() - 3..], ["Apply", "Low", "High"]);
+}
+
+#[test]
+fn hidden_menus_take_no_input() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_named_element("start", Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let center = menu.element_rect(&button).unwrap().center();
+    // Returns whether the button was clicked.
+    let click = |menu: &mut Menu| {
+        input.move_mouse(center);
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+        let clicked = button.read().has_been_pressed;
+        input.press_button(MouseButton::Right);
+        menu.update();
+        input.release_button(MouseButton::Right);
+        menu.update();
+        clicked
+    };
+
+    menu.visible = false;
+    assert!(!click(&mut menu));
+    assert!(menu.take_events().is_empty());
+
+    // Nor while fading out.
+    menu.visible = true;
+    menu.hide(Transition::Fade{ duration: 1.0, easing: Easing::Linear });
+    assert!(!click(&mut menu));
+    assert!(menu.take_events().is_empty());
+    assert!(menu.visible);
+
+    menu.show(Transition::Instant);
+    assert!(click(&mut menu));
+    assert_eq!(menu.take_events().len(), 1);
+}
+
+#[test]
+fn button_ignores_presses_outside_of_it() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    let rect = menu.element_rect(&button).unwrap();
+    input.move_mouse(vec2(rect.right() + 1.0, rect.center().y));
+    input.press();
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn check_box_toggles_on_every_click() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+
+    input.move_mouse(menu.element_rect(&check_box).unwrap().center());
+    for expected in [true, false, true] {
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+        assert_eq!(check_box.read().is_checked, expected);
+    }
+}
+
+#[test]
+fn holding_a_touch_emits_a_long_press() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_named_element("start", Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    let position = menu.element_rect(&button).unwrap().center();
+    input.touch_down(0, position);
+    let frames = (Simples_menu::input::LONG_PRESS_TIME / FRAME_TIME).ceil() as usize + 1;
+    for _ in 0..frames {
+        menu.update();
+    }
+
+    let events = menu.take_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, EventKind::LongPress);
+    assert_eq!(events[0].element, button.id());
+    assert_eq!(events[0].name.as_deref(), Some("start"));
+    assert!(menu.take_events().is_empty());
+    assert!(button.read().long_pressed);
+
+    // Held past a long-press, lifting the finger isn't a tap.
+    input.touch_up(0);
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn holding_the_mouse_emits_a_long_press() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_named_element("start", Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    input.press();
+    let frames = (Simples_menu::input::LONG_PRESS_TIME / FRAME_TIME).ceil() as usize + 1;
+    for _ in 0..frames {
+        menu.update();
+    }
+
+    let events = menu.take_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, EventKind::LongPress);
+    assert_eq!(events[0].element, button.id());
+    assert!(button.read().long_pressed);
+
+    // The mouse still clicks on release, like it does on any other button.
+    input.release();
+    menu.update();
+    assert!(button.read().has_been_pressed);
+}
+
+#[test]
+fn right_and_middle_clicks_are_flagged_and_queued() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let slot = menu.add_named_element("slot", Button::new("Sword".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+
+    input.move_mouse(menu.element_rect(&slot).unwrap().center());
+    for (button, kind) in [(MouseButton::Right, EventKind::RightClick), (MouseButton::Middle, EventKind::MiddleClick)] {
+        input.press_button(button);
+        menu.update();
+        input.release_button(button);
+        menu.update();
+        let slot = slot.read();
+        assert_eq!((slot.right_clicked, slot.middle_clicked), (kind == EventKind::RightClick, kind == EventKind::MiddleClick));
+        assert!(!slot.has_been_pressed);
+
+        let events = menu.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, kind);
+        assert_eq!(events[0].name.as_deref(), Some("slot"));
+    }
+
+    // Dragged away before letting go, it isn't a click.
+    input.press_button(MouseButton::Right);
+    menu.update();
+    input.move_mouse(vec2(0.0, 300.0));
+    input.release_button(MouseButton::Right);
+    menu.update();
+    assert!(menu.take_events().is_empty());
+}
+
+#[derive(Clickable)]
+struct Slot {
+    visible: bool,
+    position: Vec2,
+    size: Vec2,
+    color: Color,
+    visible_color: Color,
+    enabled: bool,
+    disabled_reason: Option<String>,
+}
+impl MenuElement for Slot {
+    fn update(&mut self, menu_position: Vec2) {
+        self.update_interaction(menu_position);
+    }
+    fn draw(&self, _menu_position: Vec2) {}
+    fn bounding_rect(&self) -> Option<Rect> {
+        self.visible.then(|| Rect::new(self.position.x, self.position.y, self.size.x, self.size.y))
+    }
+    fn interactive(&self) -> Option<&dyn Interactive> {
+        Some(self)
+    }
+}
+
+#[test]
+fn disabled_custom_widgets_queue_no_events() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let slot = menu.add_element(Slot{ visible: true, position: Vec2::ZERO, size: vec2(40.0, 40.0), color: GRAY, visible_color: GRAY, enabled: true, disabled_reason: Some("Empty".to_string()) });
+    let right_click = |menu: &mut Menu| {
+        input.move_mouse(menu.element_rect(&slot).unwrap().center());
+        input.press_button(MouseButton::Right);
+        menu.update();
+        input.release_button(MouseButton::Right);
+        menu.update();
+        menu.take_events().len()
+    };
+
+    assert_eq!(right_click(&mut menu), 1);
+    assert_eq!(slot.read().tooltip(), None);
+    slot.edit().enabled = false;
+    assert_eq!(right_click(&mut menu), 0);
+    assert_eq!(slot.read().tooltip().as_deref(), Some("Empty"));
+}
+
+#[test]
+fn two_quick_clicks_make_a_double_click() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let slot = menu.add_element(Button::new("Potion".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let click = |menu: &mut Menu| {
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+    };
+
+    input.move_mouse(menu.element_rect(&slot).unwrap().center());
+    click(&mut menu);
+    assert!(!slot.read().double_clicked);
+    click(&mut menu);
+    assert!(slot.read().double_clicked);
+    assert!(slot.read().has_been_pressed);
+    assert_eq!(menu.take_events().iter().map(|event| event.kind).collect::<Vec<_>>(), [EventKind::DoubleClick]);
+
+    // A third click starts over.
+    click(&mut menu);
+    assert!(!slot.read().double_clicked);
+}
+
+#[test]
+fn double_clicks_follow_the_threshold() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let slot = menu.add_element(Button::new("Potion".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    input::set_double_click_threshold(DoubleClickThreshold{ time: 5.0 * FRAME_TIME, distance: 4.0 });
+    let center = menu.element_rect(&slot).unwrap().center();
+    let click_at = |menu: &mut Menu, position: Vec2| {
+        input.move_mouse(position);
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+    };
+
+    // Too slow.
+    click_at(&mut menu, center);
+    for _ in 0..5 {
+        menu.update();
+    }
+    click_at(&mut menu, center);
+    assert!(!slot.read().double_clicked);
+
+    // Too far apart.
+    for _ in 0..5 {
+        menu.update();
+    }
+    click_at(&mut menu, center);
+    click_at(&mut menu, center + vec2(6.0, 0.0));
+    assert!(!slot.read().double_clicked);
+
+    click_at(&mut menu, center + vec2(6.0, 2.0));
+    assert!(slot.read().double_clicked);
+    input::set_double_click_threshold(DoubleClickThreshold::default());
+}
+
+#[test]
+fn focused_text_input_reads_the_keyboard() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let text_input = menu.add_element(TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0)));
+
+    // Not focused yet.
+    input.type_text("lost");
+    menu.update();
+    assert_eq!(text_input.read().text, "");
+
+    input.move_mouse(menu.element_rect(&text_input).unwrap().center());
+    input.press();
+    menu.update();
+    assert!(text_input.read().is_focused);
+
+    input.type_text("abc");
+    input.press_key(KeyCode::Backspace);
+    menu.update();
+    assert_eq!(text_input.read().text, "ab");
+    assert!(text_input.read().has_changed);
+
+    input.press_key(KeyCode::Enter);
+    menu.update();
+    assert!(!text_input.read().is_focused);
+    menu.draw();
+}
+
+#[test]
+fn disabled_elements_ignore_input() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Continue".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, vec2(0.0, 40.0), vec2(20.0, 20.0)));
+    let slider = menu.add_element(Slider::new(PositionType::TopLeft, vec2(0.0, 80.0), vec2(100.0, 10.0), 0.0, 1.0, 0.0));
+    button.edit().enabled = false;
+    check_box.edit().enabled = false;
+    slider.edit().enabled = false;
+
+    for rect in [menu.element_rect(&button), menu.element_rect(&check_box), menu.element_rect(&slider)] {
+        input.move_mouse(rect.unwrap().center());
+        input.press();
+        menu.update();
+        input.release();
+        menu.update();
+    }
+    assert!(!button.read().has_been_pressed);
+    assert!(!check_box.read().is_checked);
+    assert_eq!(slider.read().value, 0.0);
+
+    // Enabled again, they react as usual.
+    button.edit().enabled = true;
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    input.press();
+    menu.update();
+    input.release();
+    menu.update();
+    assert!(button.read().has_been_pressed);
+}
+
+#[test]
+fn disabled_text_input_takes_no_focus() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let text_input = menu.add_element(TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0)));
+
+    input.move_mouse(menu.element_rect(&text_input).unwrap().center());
+    input.press();
+    menu.update();
+    assert!(text_input.read().is_focused);
+
+    // Disabled while typing, it lets go of the keyboard.
+    text_input.edit().enabled = false;
+    input.type_text("abc");
+    menu.update();
+    assert!(!text_input.read().is_focused);
+    assert_eq!(text_input.read().text, "");
+
+    input.release();
+    menu.update();
+    input.press();
+    menu.update();
+    assert!(!text_input.read().is_focused);
+}
+
+#[test]
+fn disabled_elements_are_drawn_greyed_out_with_their_reason() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Continue".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    button.edit().enabled = false;
+    button.edit().disabled_reason = Some("No save file".to_string());
+
+    menu.update();
+    let list = testing::snapshot(&menu);
+    let colors = Simples_menu::disabled_colors();
+    assert!(list.commands.iter().any(|command| matches!(command, DrawCommand::Rectangle { color, .. } if *color == colors.background)));
+    assert!(list.commands.iter().any(|command| matches!(command, DrawCommand::Text { text, color, .. } if text == "Continue" && *color == colors.text)));
+    assert!(!list.commands.iter().any(|command| matches!(command, DrawCommand::Text { text, .. } if text == "No save file")));
+
+    input.move_mouse(menu.element_rect(&button).unwrap().center());
+    for _ in 0..=(Simples_menu::tooltip::delay() / FRAME_TIME).ceil() as usize {
+        menu.update();
+    }
+    let list = render::record(|| {
+        menu.draw();
+        Simples_menu::tooltip::draw_overlay();
+    });
+    assert!(list.commands.iter().any(|command| matches!(command, DrawCommand::Text { text, .. } if text == "No save file")));
+}
+
+#[test]
+fn disabled_elements_emit_no_long_press() {
+    let (mut menu, input) = testing::menu_with_input("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    button.edit().enabled = false;
+
+    input.touch_down(0, menu.element_rect(&button).unwrap().center());
+    let frames = (Simples_menu::input::LONG_PRESS_TIME / FRAME_TIME).ceil() as usize + 1;
+    for _ in 0..frames {
+        menu.update();
+    }
+    assert!(menu.take_events().is_empty());
+}
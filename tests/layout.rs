@@ -0,0 +1,181 @@
+use Simples_menu::macroquad::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use Simples_menu::macroquad::text::TextDimensions;
+use Simples_menu::render::{self, DrawCommand, Renderer};
+use Simples_menu::testing::{self, MockRenderer};
+use Simples_menu::{Button, CheckBox, Element, Menu, MenuElement, PositionType, TextLabel};
+
+///Return where `child` lays its elements out from, relative to where its parent lays out its own.
+fn content_origin(child: &Element<Menu>, grandchild: &Element<Menu>) -> Vec2 {
+    child.read().element_rect(grandchild).unwrap().point() - grandchild.read().bounding_rect().unwrap().point()
+}
+
+#[test]
+fn elements_are_laid_out_inside_the_menu() {
+    let mut menu = testing::menu("Menu", vec2(100.0, 100.0));
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, vec2(0.0, 40.0), vec2(20.0, 20.0)));
+
+    let menu_rect = menu.screen_rect().unwrap();
+    let button_rect = menu.element_rect(&button).unwrap();
+    let check_box_rect = menu.element_rect(&check_box).unwrap();
+    assert!(menu_rect.contains(button_rect.point()) && menu_rect.contains(button_rect.point() + button_rect.size()));
+    assert!(menu_rect.contains(check_box_rect.point()) && menu_rect.contains(check_box_rect.point() + check_box_rect.size()));
+    assert_eq!(check_box_rect.size(), vec2(20.0, 20.0));
+    assert_eq!(check_box_rect.y - button_rect.y, 40.0);
+
+    // Buttons without a size fit their title, which the mock renderer measures at half the font size per character.
+    let title_size = button.read().title.font_size / 2.0 * "Start".len() as f32;
+    assert_eq!(button_rect.w, title_size + 10.0);
+}
+
+#[test]
+fn fitted_buttons_refit_only_when_their_title_changes() {
+    let (mut menu, _input) = testing::menu_with_input("Menu", Vec2::ZERO);
+    let button = menu.add_element(Button::new("Start".to_string(), PositionType::Center, vec2(100.0, 50.0), None));
+    let center = button.read().position + button.read().size / 2.0;
+
+    button.edit().title.name = "Start the game".to_string();
+    menu.update();
+    menu.update();
+    let (position, size) = (button.read().position, button.read().size);
+    assert_eq!(size.x, button.read().title.font_size / 2.0 * "Start the game".len() as f32 + 10.0);
+    assert_eq!(position + size / 2.0, center);
+
+    // A size set by hand is kept, and the button no longer follows its title.
+    button.edit().size = vec2(40.0, 20.0);
+    button.edit().title.name = "Go".to_string();
+    menu.update();
+    menu.update();
+    assert_eq!(button.read().size, vec2(40.0, 20.0));
+    assert_eq!(button.read().position, position);
+}
+
+// A `MockRenderer` counting the texts it measures.
+struct CountingRenderer {
+    measured: Rc<Cell<usize>>,
+}
+impl Renderer for CountingRenderer {
+    fn draw_rectangle(&mut self, rect: Rect, color: Color) {
+        MockRenderer.draw_rectangle(rect, color)
+    }
+
+    fn draw_circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        MockRenderer.draw_circle(center, radius, color)
+    }
+
+    fn draw_text(&mut self, text: &str, position: Vec2, font_size: f32, color: Color) {
+        MockRenderer.draw_text(text, position, font_size, color)
+    }
+
+    fn measure_text(&mut self, text: &str, font_size: u16) -> TextDimensions {
+        self.measured.set(self.measured.get() + 1);
+        MockRenderer.measure_text(text, font_size)
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) {
+        MockRenderer.set_clip(clip)
+    }
+}
+
+#[test]
+fn text_labels_are_measured_only_when_their_title_changes() {
+    let (mut menu, _input) = testing::menu_with_input("Menu", Vec2::ZERO);
+    let label = menu.add_element(TextLabel::new("Score".to_string(), PositionType::TopLeft, Vec2::ZERO));
+    let measured = Rc::new(Cell::new(0));
+    render::set_renderer(CountingRenderer{ measured: measured.clone() });
+    menu.update();
+    let first_frame = measured.get();
+
+    for _ in 0..10 {
+        menu.update();
+    }
+    assert_eq!(measured.get(), first_frame);
+
+    label.edit().title.name = "High score".to_string();
+    menu.update();
+    menu.update();
+    assert_eq!(measured.get(), first_frame + 1);
+    assert_eq!(menu.element_rect(&label).unwrap().w, label.read().title.font_size / 2.0 * "High score".len() as f32);
+
+    label.edit().title.font_size = 20.0;
+    menu.update();
+    menu.update();
+    assert_eq!(measured.get(), first_frame + 2);
+}
+
+#[test]
+fn rects_follow_the_menu_position_and_scale() {
+    let mut menu = testing::menu("Menu", vec2(100.0, 100.0));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, vec2(10.0, 10.0), vec2(20.0, 20.0)));
+    let before = menu.element_rect(&check_box).unwrap();
+
+    menu.position += vec2(50.0, 25.0);
+    let moved = menu.element_rect(&check_box).unwrap();
+    assert_eq!(moved.point() - before.point(), vec2(50.0, 25.0));
+
+    menu.scale = 2.0;
+    let scaled = menu.element_rect(&check_box).unwrap();
+    assert_eq!(scaled.size(), vec2(40.0, 40.0));
+    assert_eq!(scaled.point() - menu.position, (moved.point() - menu.position) * 2.0);
+}
+
+#[test]
+fn hidden_and_foreign_elements_have_no_rect() {
+    let mut menu = testing::menu("Menu", Vec2::ZERO);
+    let mut other = Menu::new("Other".to_string(), Vec2::ZERO);
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    let foreign = other.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+
+    assert!(menu.element_rect(&foreign).is_none());
+    menu.collapsed = true;
+    assert!(menu.element_rect(&check_box).is_none());
+    menu.visible = false;
+    assert!(menu.screen_rect().is_none());
+}
+
+#[test]
+fn clicks_reach_elements_of_menus_nested_three_levels_deep() {
+    let (mut root, input) = testing::menu_with_input("Menu", vec2(50.0, 50.0));
+    let root_button = root.add_element(Button::new("Root".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let child = root.add_element(Menu::new("Child".to_string(), vec2(0.0, 60.0)));
+    let grandchild = child.edit().add_element(Menu::new("Grandchild".to_string(), vec2(20.0, 40.0)));
+    let button = grandchild.edit().add_element(Button::new("Deep".to_string(), PositionType::TopLeft, vec2(10.0, 10.0), None));
+
+    // The root lays its elements out from where its first element is drawn.
+    let root_origin = root.element_rect(&root_button).unwrap().point();
+    let child_origin = content_origin(&child, &grandchild);
+    let button_rect = grandchild.read().element_rect(&button).unwrap().offset(root_origin + child_origin);
+
+    input.move_mouse(button_rect.center());
+    input.press();
+    root.update();
+    input.release();
+    root.update();
+    assert!(button.read().has_been_pressed);
+    assert!(!root_button.read().has_been_pressed);
+
+    // Moving a nested menu moves what it hit tests.
+    grandchild.edit().position.x += 500.0;
+    input.press();
+    root.update();
+    input.release();
+    root.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn elements_of_menus_nested_three_levels_deep_are_drawn_where_they_are_hit() {
+    let (mut root, _input) = testing::menu_with_input("Menu", vec2(50.0, 50.0));
+    let root_button = root.add_element(Button::new("Root".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let child = root.add_element(Menu::new("Child".to_string(), vec2(0.0, 60.0)));
+    let grandchild = child.edit().add_element(Menu::new("Grandchild".to_string(), vec2(20.0, 40.0)));
+    let check_box = grandchild.edit().add_element(CheckBox::new(PositionType::TopLeft, vec2(10.0, 10.0), vec2(20.0, 20.0)));
+    root.update();
+
+    let root_origin = root.element_rect(&root_button).unwrap().point();
+    let hit_rect = grandchild.read().element_rect(&check_box).unwrap().offset(root_origin + content_origin(&child, &grandchild));
+    let drawn = testing::snapshot(&root).commands.into_iter().any(|command| matches!(command, DrawCommand::Rectangle { rect, .. } if rect == hit_rect));
+    assert!(drawn, "no rectangle drawn at {hit_rect:?}");
+}
@@ -0,0 +1,73 @@
+use Simples_menu::loader::{self, LoadError};
+use Simples_menu::testing;
+
+#[cfg(feature = "ron")]
+fn ron_error(source: &str) -> LoadError {
+    match loader::load_ron(source) {
+        Ok(_) => panic!("loaded {source:?}"),
+        Err(error) => error,
+    }
+}
+
+#[cfg(feature = "json")]
+fn json_error(source: &str) -> LoadError {
+    match loader::load_json(source) {
+        Ok(_) => panic!("loaded {source:?}"),
+        Err(error) => error,
+    }
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn ron_errors_point_at_the_bad_definition() {
+    // Duplicate names are found while building the menu, which measures its titles.
+    testing::headless();
+    let error = ron_error("Menu(\n    title: \"Main\",\n    position: (0.0, 0.0),\n    elements: [\n        Slider(position: (0.0, 0.0)),\n    ],\n)");
+    assert_eq!(error.position.map(|(line, _)| line), Some(5), "{error}");
+    assert!(error.message.contains("Slider"), "{error}");
+
+    let error = ron_error("Menu(\n    title: \"Main\",\n    position: (0.0, 0.0),\n    elements: [\n        Button(label: \"Start\", position: (0.0, 0.0), colour: (1.0, 1.0, 1.0, 1.0)),\n    ],\n)");
+    assert_eq!(error.position.map(|(line, _)| line), Some(5), "{error}");
+    assert!(error.message.contains("colour"), "{error}");
+
+    let error = ron_error("Menu(\n    title: \"Main\",\n    position: (0.0, 0.0),\n    elements: [\n        Button(name: \"start\", label: \"Start\", position: (0.0, 0.0)),\n        TextLabel(name: \"start\", label: \"Start\", position: (0.0, 20.0)),\n    ],\n)");
+    assert_eq!(error.position.map(|(line, _)| line), Some(6), "{error}");
+    assert!(error.message.contains("duplicate element name \"start\""), "{error}");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_errors_point_at_the_bad_definition() {
+    // Duplicate names are found while building the menu, which measures its titles.
+    testing::headless();
+    let error = json_error("{\"Menu\": {\"title\": \"Main\", \"position\": [0.0, 0.0], \"elements\": [\n{\"Slider\": {\"position\": [0.0, 0.0]}}\n]}}");
+    assert_eq!(error.position.map(|(line, _)| line), Some(2), "{error}");
+    assert!(error.message.contains("Slider"), "{error}");
+
+    let error = json_error("{\"Menu\": {\"title\": \"Main\", \"position\": [0.0, 0.0], \"elements\": [\n{\"Button\": {\"label\": \"Start\", \"position\": [0.0, 0.0], \"colour\": [1.0, 1.0, 1.0, 1.0]}}\n]}}");
+    assert_eq!(error.position.map(|(line, _)| line), Some(2), "{error}");
+    assert!(error.message.contains("colour"), "{error}");
+
+    let error = json_error("{\"Menu\": {\"title\": \"Main\", \"position\": [0.0, 0.0], \"elements\": [\n{\"Menu\": {\"name\": \"audio\", \"title\": \"Audio\", \"position\": [0.0, 0.0], \"elements\": [\n{\"CheckBox\": {\"name\": \"audio\", \"position\": [0.0, 0.0], \"size\": [20.0, 20.0]}}\n]}}\n]}}");
+    assert_eq!(error.position.map(|(line, _)| line), Some(3), "{error}");
+    assert!(error.message.contains("duplicate element name \"audio\""), "{error}");
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn names_can_be_reused_across_loads() {
+    testing::headless();
+    let source = "Menu(title: \"Main\", position: (0.0, 0.0), elements: [Button(name: \"start\", label: \"Start\", position: (0.0, 0.0))])";
+    assert!(loader::load_ron(source).is_ok());
+    assert!(loader::load_ron(source).unwrap().button("start").is_some());
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn the_root_must_be_an_unnamed_menu() {
+    let error = ron_error("Button(label: \"Start\", position: (0.0, 0.0))");
+    assert_eq!(error.message, "the root element must be a Menu, found a Button");
+
+    let error = ron_error("Menu(name: \"main\", title: \"Main\", position: (0.0, 0.0))");
+    assert!(error.message.contains("can't be named"), "{error}");
+}
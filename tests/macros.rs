@@ -0,0 +1,139 @@
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::testing;
+use Simples_menu::PositionType::TopLeft;
+use Simples_menu::{menu, Button, CheckBox, Menu, MenuChoice, MenuForm, TextLabel};
+use macros::BoundingRect;
+
+#[derive(MenuChoice, Clone, Copy, PartialEq, Debug)]
+enum Quality {
+    Low,
+    #[menu(label = "Very high")]
+    VeryHigh,
+}
+
+#[derive(MenuForm)]
+struct Settings {
+    vsync: bool,
+    #[menu(min = 60, max = 120)]
+    fov: u32,
+    max_fps: u32,
+    player_name: String,
+    quality: Quality,
+    #[menu(skip)]
+    launches: u32,
+}
+
+fn settings() -> Settings {
+    Settings{
+        vsync: true,
+        fov: 90,
+        max_fps: 144,
+        player_name: "Player".to_string(),
+        quality: Quality::VeryHigh,
+        launches: 3,
+    }
+}
+
+#[test]
+fn menus_are_declared_with_their_elements() {
+    testing::headless();
+    menu! {
+        let main = Menu("Main", Vec2{ x: 10.0, y: 20.0 }) [color: BLUE] {
+            let start = Button("Start", TopLeft, Vec2::ZERO, None) [enabled: false];
+            TextLabel("Unnamed", TopLeft, Vec2{ x: 0.0, y: 40.0 });
+            let options = Menu("Options", Vec2{ x: 0.0, y: 80.0 }) {
+                let vsync = CheckBox(TopLeft, Vec2::ZERO, Vec2{ x: 20.0, y: 20.0 });
+            }
+        }
+    }
+
+    assert_eq!(main.title.name, "Main");
+    assert_eq!(main.color, BLUE);
+    assert_eq!(main.elements.len(), 3);
+    assert_eq!(start.read().title.name, "Start");
+    assert!(!start.read().enabled);
+    assert_eq!(main.find("start").map(|element| element.id()), Some(start.id()));
+    assert_eq!(options.read().elements.len(), 1);
+    assert_eq!(vsync.name(), Some("vsync"));
+    assert_eq!(main.find("options").map(|element| element.id()), Some(options.id()));
+}
+
+#[test]
+fn choices_are_listed_with_their_labels() {
+    let labels: Vec<String> = Quality::choices().iter().map(Quality::label).collect();
+    assert_eq!(labels, ["Low", "Very high"]);
+}
+
+#[test]
+fn forms_show_the_current_values() {
+    testing::headless();
+    let form = settings().to_menu("Options", Vec2::ZERO);
+
+    assert!(form.vsync.read().is_checked);
+    assert_eq!(form.fov.read().value, 90.0);
+    assert_eq!(form.fov.read().step, Some(1.0));
+    assert_eq!(form.max_fps.read().text, "144");
+    assert_eq!(form.player_name.read().text, "Player");
+    assert_eq!(form.quality.read().selected_option(), Some("Very high"));
+    assert!(form.menu.find("launches").is_none());
+}
+
+#[test]
+fn forms_write_the_edited_values_back() {
+    testing::headless();
+    let mut settings = settings();
+    let form = settings.to_menu("Options", Vec2::ZERO);
+    form.vsync.edit().is_checked = false;
+    form.fov.edit().value = 100.4;
+    form.max_fps.edit().text = "60".to_string();
+    form.player_name.edit().text = "Ana".to_string();
+    form.quality.edit().selected = 0;
+
+    settings.read_menu(&form);
+    assert!(!settings.vsync);
+    assert_eq!(settings.fov, 100);
+    assert_eq!(settings.max_fps, 60);
+    assert_eq!(settings.player_name, "Ana");
+    assert_eq!(settings.quality, Quality::Low);
+    assert_eq!(settings.launches, 3);
+
+    // Text that isn't a number leaves the field as it was.
+    form.max_fps.edit().text = "fast".to_string();
+    settings.read_menu(&form);
+    assert_eq!(settings.max_fps, 60);
+}
+
+#[derive(BoundingRect)]
+struct Panel<T: Copy> {
+    visible: bool,
+    position: Vec2,
+    size: Vec2,
+    content: T,
+}
+
+#[derive(BoundingRect)]
+#[bounding(visible = "shown", pos = "origin", size = "extent")]
+struct Marker {
+    shown: bool,
+    origin: Vec2,
+    extent: Vec2,
+}
+
+#[test]
+fn bounding_rects_are_derived_for_generic_structs() {
+    let mut panel = Panel{ visible: true, position: vec2(10.0, 20.0), size: vec2(30.0, 40.0), content: 7 };
+    assert_eq!(panel.bounding_rect(), Some(Rect::new(10.0, 20.0, 30.0, 40.0)));
+    assert_eq!(panel.content, 7);
+
+    panel.visible = false;
+    assert_eq!(panel.bounding_rect(), None);
+}
+
+#[test]
+fn bounding_rects_are_derived_from_renamed_fields() {
+    let mut marker = Marker{ shown: true, origin: vec2(5.0, 6.0), extent: vec2(7.0, 8.0) };
+    assert_eq!(marker.bounding_rect(), Some(Rect::new(5.0, 6.0, 7.0, 8.0)));
+
+    marker.shown = false;
+    assert_eq!(marker.bounding_rect(), None);
+}
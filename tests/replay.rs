@@ -0,0 +1,145 @@
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::input::{ButtonState, KeyEvent, KeyInput, PointerKind, RawPointer, MOUSE_ID};
+use Simples_menu::replay::{InputRecording, RecordedFrame};
+use Simples_menu::testing::{self, TestInput};
+use Simples_menu::{Button, CheckBox, Element, Menu, MenuEvent, PositionType, TextInput};
+
+struct Form {
+    menu: Menu,
+    check_box: Element<CheckBox>,
+    name: Element<TextInput>,
+    button: Element<Button>,
+}
+
+fn form() -> Form {
+    let mut menu = testing::menu("Form", vec2(100.0, 100.0));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    let name = menu.add_element(TextInput::new(PositionType::TopLeft, vec2(0.0, 30.0), vec2(100.0, 20.0)));
+    let button = menu.add_named_element("save", Button::new("Save".to_string(), PositionType::TopLeft, vec2(0.0, 60.0), None));
+    Form{ menu, check_box, name, button }
+}
+
+///Drive a form through a session, with uneven frame times, and return what it recorded and the events it emitted.
+fn record_session() -> (Form, InputRecording, Vec<MenuEvent>) {
+    let mut form = form();
+    let input = TestInput::new();
+    form.menu.set_input(input.clone());
+    let mut frame = 0;
+    form.menu.animator.set_clock(move || {
+        frame += 1;
+        if frame % 3 == 0 { 0.1 } else { 0.02 }
+    });
+    form.menu.start_recording();
+
+    let update = |form: &mut Form| form.menu.update();
+    input.move_mouse(form.menu.element_rect(&form.check_box).unwrap().center());
+    input.press();
+    update(&mut form);
+    input.release();
+    update(&mut form);
+
+    input.move_mouse(form.menu.element_rect(&form.name).unwrap().center());
+    input.press();
+    update(&mut form);
+    input.release();
+    input.type_text("Ada!");
+    update(&mut form);
+    input.press_key(KeyCode::Backspace);
+    update(&mut form);
+
+    input.touch_down(3, form.menu.element_rect(&form.button).unwrap().center());
+    for _ in 0..20 {
+        update(&mut form);
+    }
+    input.touch_up(3);
+    update(&mut form);
+
+    let recording = form.menu.stop_recording().unwrap();
+    let events = form.menu.take_events();
+    (form, recording, events)
+}
+
+#[test]
+fn replay_reproduces_the_session() {
+    let (recorded, recording, recorded_events) = record_session();
+    assert_eq!(recording.frames.len(), 26);
+    assert!(recorded.check_box.read().is_checked);
+    assert_eq!(recorded.name.read().text, "Ada");
+    assert_eq!(recorded_events.len(), 1);
+
+    // Replayed from the saved text into a new form, updated at its own pace.
+    let recording = InputRecording::from_text(&recording.to_text()).unwrap();
+    let mut replayed = form();
+    let replay = replayed.menu.replay(recording);
+    while !replay.is_finished() {
+        replayed.menu.update();
+    }
+
+    assert_eq!(replay.frames_played(), 26);
+    assert_eq!(replayed.check_box.read().is_checked, recorded.check_box.read().is_checked);
+    assert_eq!(replayed.name.read().text, recorded.name.read().text);
+    assert_eq!(replayed.menu.take_events(), recorded_events);
+}
+
+#[test]
+fn replay_uses_the_recorded_time_steps() {
+    let (_, recording, _) = record_session();
+    let mut replayed = form();
+    // The replay's time steps drive the menu, whatever clock it had.
+    replayed.menu.animator.set_clock(|| 10.0);
+    replayed.menu.replay(recording);
+
+    // The long-press happens on the same frame as when recorded, not on the first one held.
+    let mut long_press_frame = None;
+    for frame in 0..26 {
+        replayed.menu.update();
+        if !replayed.menu.take_events().is_empty() {
+            long_press_frame = Some(frame);
+        }
+    }
+    assert!(long_press_frame.unwrap() > 10);
+}
+
+#[test]
+fn recording_round_trips_through_text() {
+    let mut mouse = RawPointer::new(MOUSE_ID, PointerKind::Mouse, vec2(10.5, -3.25));
+    mouse.down = true;
+    mouse.pressed = true;
+    mouse.middle = ButtonState{ down: true, pressed: true, released: false };
+    let mut touch = RawPointer::new(7, PointerKind::Touch, vec2(1.0, 2.0));
+    touch.released = true;
+    let recording = InputRecording{
+        frames: vec![
+            RecordedFrame{
+                delta: 1.0 / 60.0,
+                pointers: vec![mouse],
+                keys: KeyInput{ events: vec![KeyEvent::Char('a'), KeyEvent::Key(KeyCode::Enter), KeyEvent::Char('\\'), KeyEvent::Char('\n'), KeyEvent::Key(KeyCode::Escape), KeyEvent::Key(KeyCode::Backspace), KeyEvent::Char('c')] },
+            },
+            RecordedFrame{
+                delta: 0.1,
+                pointers: vec![touch],
+                keys: KeyInput::default(),
+            },
+        ],
+    };
+
+    let text = recording.to_text();
+    assert_eq!(InputRecording::from_text(&text).unwrap(), recording);
+    assert_eq!(text.lines().next(), Some("frame 0.016666668"));
+}
+
+#[test]
+fn invalid_recordings_are_rejected_with_their_line() {
+    let error = InputRecording::from_text("frame 0.1\n\n# comment\npointer 1 pen 0 0\n").unwrap_err();
+    assert!(error.to_string().starts_with("line 4:"), "{}", error);
+    assert!(InputRecording::from_text("pointer 1 mouse 0 0\n").is_err());
+    assert!(InputRecording::from_text("frame 0.1\nkey NotAKey\n").is_err());
+}
+
+#[test]
+fn stop_recording_without_starting_returns_nothing() {
+    let mut form = form();
+    testing::attach(&mut form.menu);
+    form.menu.update();
+    assert!(form.menu.stop_recording().is_none());
+}
@@ -0,0 +1,102 @@
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::render::DrawCommand;
+use Simples_menu::testing::{self, assert_snapshot};
+use Simples_menu::{debug, Button, CheckBox, Menu, PositionType, Slider, TextLabel};
+
+fn menu() -> Menu {
+    let mut menu = testing::menu("Settings", vec2(100.0, 100.0));
+    menu.add_element(TextLabel::new("Volume".to_string(), PositionType::TopLeft, Vec2::ZERO));
+    menu.add_element(Slider::new(PositionType::TopLeft, vec2(0.0, 20.0), vec2(120.0, 10.0), 0.0, 1.0, 0.5));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, vec2(0.0, 40.0), vec2(20.0, 20.0)));
+    check_box.edit().is_checked = true;
+    menu.add_owned_element(None, Button::new("Apply".to_string(), PositionType::Center, vec2(60.0, 90.0), None));
+    menu
+}
+
+#[test]
+fn menu_draws_like_its_snapshot() {
+    let menu = menu();
+    assert_snapshot("settings_menu", &testing::snapshot(&menu));
+}
+
+#[test]
+fn scaled_menu_draws_like_its_snapshot() {
+    let mut menu = menu();
+    menu.scale = 2.0;
+    assert_snapshot("settings_menu_scaled", &testing::snapshot(&menu));
+}
+
+#[test]
+fn title_is_centered_over_the_elements() {
+    let menu = menu();
+    let list = testing::snapshot(&menu);
+
+    let background = list.commands.iter().find_map(|command| match command {
+        DrawCommand::Rectangle { rect, .. } => Some(*rect),
+        _ => None,
+    }).unwrap();
+    let (position, font_size) = list.commands.iter().find_map(|command| match command {
+        DrawCommand::Text { text, position, font_size, .. } if text == "Settings" => Some((*position, *font_size)),
+        _ => None,
+    }).unwrap();
+
+    // The mock renderer measures every character half of the font size wide.
+    let title_width = "Settings".len() as f32 * font_size / 2.0;
+    let left = position.x - background.x;
+    let right = background.right() - (position.x + title_width);
+    assert!((left - right).abs() < 0.01, "title is {left} from the left edge and {right} from the right one");
+}
+
+#[test]
+fn hidden_menu_draws_nothing() {
+    let mut menu = menu();
+    menu.visible = false;
+    assert!(testing::snapshot(&menu).commands.is_empty());
+}
+
+#[test]
+fn debug_overlay_draws_like_its_snapshot() {
+    let mut menu = menu();
+    let input = testing::attach(&mut menu);
+    // Kept so the menu doesn't drop the elements added without a handle on update.
+    let elements = menu.elements.clone();
+    let check_box = &elements[2];
+    input.move_mouse(menu.element_rect(check_box).unwrap().center());
+    menu.update();
+
+    debug::set_overlay(true);
+    let list = testing::snapshot(&menu);
+    debug::set_overlay(false);
+    assert_snapshot("settings_menu_debug", &list);
+}
+
+#[test]
+fn debug_overlay_labels_and_highlights_elements() {
+    let mut menu = menu();
+    let input = testing::attach(&mut menu);
+    // Kept so the menu doesn't drop the elements added without a handle on update.
+    let elements = menu.elements.clone();
+    let check_box = &elements[2];
+    let check_box_rect = menu.element_rect(check_box).unwrap();
+    input.move_mouse(check_box_rect.center());
+    menu.update();
+
+    debug::set_overlay(true);
+    let list = testing::snapshot(&menu);
+    debug::set_overlay(false);
+
+    let labels: Vec<&str> = list.commands.iter().filter_map(|command| match command {
+        DrawCommand::Text { text, .. } => Some(text.as_str()),
+        _ => None,
+    }).collect();
+    for label in ["#0 TextLabel", "#1 Slider", "#2 CheckBox", "#3 Button", "menu", "title", "edge 20"] {
+        assert!(labels.contains(&label), "no label {label} in {labels:?}");
+    }
+    let highlighted: Vec<Rect> = list.commands.iter().filter_map(|command| match command {
+        DrawCommand::Rectangle { rect, color } if *color == debug::HOVERED_COLOR => Some(*rect),
+        _ => None,
+    }).collect();
+    assert_eq!(highlighted, vec![check_box_rect]);
+
+    assert!(testing::snapshot(&menu).commands.iter().all(|command| !matches!(command, DrawCommand::Text { text, .. } if text == "menu")));
+}
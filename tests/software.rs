@@ -0,0 +1,56 @@
+#![cfg(feature = "software")]
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::render::{set_renderer, Renderer};
+use Simples_menu::software::{screenshot, SoftwareRenderer};
+use Simples_menu::testing;
+use Simples_menu::{CheckBox, Menu, PositionType, Slider};
+
+fn menu() -> Menu {
+    let mut menu = testing::menu("Settings", vec2(40.0, 40.0));
+    // Elements measure their text when built, so the software renderer has to be set before adding them.
+    set_renderer(SoftwareRenderer::new(1, 1));
+    let check_box = menu.add_owned_element(None, CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    check_box.edit().is_checked = true;
+    menu.add_owned_element(None, Slider::new(PositionType::TopLeft, vec2(0.0, 30.0), vec2(120.0, 10.0), 0.0, 1.0, 0.5));
+    menu
+}
+
+fn bytes(color: Color) -> [u8; 4] {
+    color.into()
+}
+
+#[test]
+fn screenshot_draws_the_menu() {
+    let menu = menu();
+    let image = screenshot(&menu, 320, 240);
+    assert_eq!((image.width(), image.height()), (320, 240));
+    assert_eq!(image.pixels().len(), 320 * 240 * 4);
+
+    // Outside of the menu the image stays transparent.
+    assert_eq!(image.pixel(0, 0).unwrap().a, 0.0);
+    // The background, with the check box and its mark over it.
+    let check_box = menu.element_rect(&menu.elements[0]).unwrap();
+    assert_eq!(bytes(image.pixel(check_box.x as u32 - 5, check_box.y as u32 + 5).unwrap()), bytes(menu.color));
+    assert_eq!(bytes(image.pixel(check_box.x as u32 + 1, check_box.y as u32 + 1).unwrap()), bytes(LIGHTGRAY));
+    assert_eq!(bytes(image.pixel(check_box.center().x as u32, check_box.center().y as u32).unwrap()), bytes(GREEN));
+}
+
+#[test]
+fn screenshot_is_a_png() {
+    let menu = menu();
+    let png = screenshot(&menu, 64, 64).encode_png().unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+}
+
+#[test]
+fn text_is_drawn_in_its_color() {
+    let mut renderer = SoftwareRenderer::new(64, 32);
+    renderer.draw_text("MM", vec2(2.0, 20.0), 16.0, RED);
+    let pixels = renderer.pixels();
+    let drawn: Vec<&[u8]> = pixels.chunks(4).filter(|pixel| pixel[3] > 0).collect();
+    assert!(!drawn.is_empty());
+    assert!(drawn.iter().all(|pixel| pixel[0] > pixel[1] && pixel[0] > pixel[2]));
+
+    let size = renderer.measure_text("MM", 16);
+    assert!(size.width > 0.0 && size.height > 0.0);
+}
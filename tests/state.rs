@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::PathBuf;
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::testing;
+use Simples_menu::{CheckBox, Menu, MenuState, PositionType, Slider, TextInput};
+
+// A file of its own in the temporary directory for each test, as they run in parallel.
+fn state_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("simples_menu_{}_{}.state", name, std::process::id()))
+}
+
+#[test]
+fn awkward_names_and_text_survive_a_save() {
+    let path = state_path("awkward");
+    let texts = ["a = b", "ends with =", "# not a comment", "line\nbreak\r", "back\\slash\\", "trailing \r"];
+    let mut saved = testing::menu("Options", vec2(10.0, 20.0));
+    let inputs: Vec<_> = texts.iter().enumerate().map(|(index, text)| {
+        let input = saved.add_named_element(&format!("{}{}", text, index), TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0)));
+        input.edit().text = text.to_string();
+        input
+    }).collect();
+    let nested = saved.add_named_element("#sound =", Menu::new("Sound".to_string(), Vec2::ZERO));
+    let muted = nested.edit().add_named_element("muted = ", CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    muted.edit().is_checked = true;
+    saved.save_state(&path).unwrap();
+
+    let mut loaded = testing::menu("Options", vec2(10.0, 20.0));
+    loaded.position = Vec2::ZERO;
+    let loaded_inputs: Vec<_> = texts.iter().enumerate()
+        .map(|(index, text)| loaded.add_named_element(&format!("{}{}", text, index), TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0))))
+        .collect();
+    let nested = loaded.add_named_element("#sound =", Menu::new("Sound".to_string(), Vec2::ZERO));
+    let loaded_muted = nested.edit().add_named_element("muted = ", CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    loaded.load_state(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.position, vec2(10.0, 20.0));
+    for (input, loaded_input) in inputs.iter().zip(&loaded_inputs) {
+        assert_eq!(loaded_input.read().text, input.read().text);
+    }
+    assert!(loaded_muted.read().is_checked);
+    assert_eq!(MenuState::from_text(&saved.state().to_text()).unwrap(), saved.state());
+}
+
+#[test]
+fn missing_and_unknown_entries_are_left_alone() {
+    let path = state_path("changed");
+    let mut saved = testing::menu("Options", vec2(10.0, 20.0));
+    let volume = saved.add_named_element("volume", Slider::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0), 0.0, 1.0, 0.3));
+    let _removed = saved.add_named_element("removed", CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    volume.edit().value = 0.75;
+    saved.save_state(&path).unwrap();
+
+    // The new version of the menu dropped one element and added another.
+    let mut loaded = testing::menu("Options", vec2(10.0, 20.0));
+    let loaded_volume = loaded.add_named_element("volume", Slider::new(PositionType::TopLeft, Vec2::ZERO, vec2(100.0, 20.0), 0.0, 1.0, 0.3));
+    let added = loaded.add_named_element("added", CheckBox::new(PositionType::TopLeft, Vec2::ZERO, vec2(20.0, 20.0)));
+    added.edit().is_checked = true;
+    loaded.load_state(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded_volume.read().value, 0.75);
+    assert!(added.read().is_checked);
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+    let error = MenuState::from_text("# saved state\n\n:collapsed = false\nvolume 0.5\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 4: expected `key = value`");
+}
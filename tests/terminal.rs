@@ -0,0 +1,106 @@
+#![cfg(feature = "terminal")]
+use Simples_menu::terminal::crossterm::event::{Event, KeyCode as TermKey, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::render::set_renderer;
+use Simples_menu::terminal::{TerminalInput, TerminalRenderer, CELL_SIZE};
+use Simples_menu::{Button, CheckBox, Menu, PositionType, TextInput, TextLabel};
+
+fn renderer() -> TerminalRenderer {
+    let renderer = TerminalRenderer::new(40, 12);
+    set_renderer(renderer.clone());
+    renderer
+}
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent{ kind, column, row, modifiers: KeyModifiers::NONE })
+}
+
+fn cell_of(rect: Rect) -> (u16, u16) {
+    ((rect.center().x / CELL_SIZE.x) as u16, (rect.center().y / CELL_SIZE.y) as u16)
+}
+
+#[test]
+fn menu_is_drawn_with_characters() {
+    let renderer = renderer();
+    let mut menu = Menu::new("Server".to_string(), vec2(16.0, 32.0));
+    menu.add_element(TextLabel::new("Open".to_string(), PositionType::TopLeft, Vec2::ZERO));
+    let check_box = menu.add_element(CheckBox::new(PositionType::TopLeft, vec2(48.0, 0.0), vec2(16.0, 16.0)));
+    check_box.edit().is_checked = true;
+    menu.add_element(Button::new("Quit".to_string(), PositionType::TopLeft, vec2(0.0, 32.0), None));
+    menu.draw();
+
+    let text = renderer.text();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert!(lines.iter().all(|line| line.chars().count() == 40));
+    assert!(text.contains("Server"));
+    assert!(text.contains("Open"));
+    assert!(text.contains("Quit"));
+    assert!(text.contains('●'));
+
+    // Every character of the title takes one cell.
+    let title_row = lines.iter().find(|line| line.contains("Server")).unwrap();
+    assert!(title_row.contains("Server "));
+}
+
+#[test]
+fn buttons_are_clicked_with_the_terminal_mouse() {
+    renderer();
+    let mut menu = Menu::new("Server".to_string(), vec2(16.0, 32.0));
+    let button = menu.add_element(Button::new("Restart".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    let input = TerminalInput::new();
+    menu.set_input(input.clone());
+    menu.animator.set_clock(|| 1.0 / 60.0);
+
+    let (column, row) = cell_of(menu.element_rect(&button).unwrap());
+    input.begin_frame();
+    input.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), column, row));
+    menu.update();
+    assert!(button.read().is_pressed);
+
+    input.begin_frame();
+    input.handle_event(&mouse(MouseEventKind::Up(MouseButton::Left), column, row));
+    menu.update();
+    assert!(button.read().has_been_pressed);
+    assert!(!button.read().is_pressed);
+
+    // Away from the button.
+    input.begin_frame();
+    input.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), 0, 0));
+    menu.update();
+    assert!(!button.read().has_been_pressed);
+}
+
+#[test]
+fn text_inputs_read_the_terminal_keyboard() {
+    renderer();
+    let mut menu = Menu::new("Server".to_string(), vec2(16.0, 32.0));
+    let name = menu.add_element(TextInput::new(PositionType::TopLeft, Vec2::ZERO, vec2(120.0, 16.0)));
+    let input = TerminalInput::new();
+    menu.set_input(input.clone());
+    menu.animator.set_clock(|| 1.0 / 60.0);
+
+    let (column, row) = cell_of(menu.element_rect(&name).unwrap());
+    input.begin_frame();
+    input.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), column, row));
+    menu.update();
+    assert!(name.read().is_focused);
+
+    input.begin_frame();
+    for key in [TermKey::Char('a'), TermKey::Char('b'), TermKey::Backspace, TermKey::Char('c')] {
+        input.handle_event(&Event::Key(KeyEvent::new(key, KeyModifiers::NONE)));
+    }
+    // Shortcuts aren't typed.
+    input.handle_event(&Event::Key(KeyEvent::new(TermKey::Char('x'), KeyModifiers::CONTROL)));
+    assert_eq!(input.keys().typed(), vec!['a', 'b', 'c']);
+    assert!(input.keys().pressed(KeyCode::Backspace));
+    assert!(input.keys().pressed(KeyCode::X));
+    menu.update();
+    // Keys are read in the order they came.
+    assert_eq!(name.read().text, "ac");
+
+    input.begin_frame();
+    input.handle_event(&Event::Key(KeyEvent::new(TermKey::Esc, KeyModifiers::NONE)));
+    menu.update();
+    assert!(!name.read().is_focused);
+}
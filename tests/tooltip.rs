@@ -0,0 +1,129 @@
+use Simples_menu::macroquad::prelude::*;
+use Simples_menu::render::{self, DisplayList, DrawCommand};
+use Simples_menu::testing::{self, TestInput, FRAME_TIME, SCREEN_SIZE};
+use Simples_menu::tooltip::{self, Tooltip};
+use Simples_menu::{Button, Element, Menu, PositionType, TextLabel};
+
+///A toolbar with a save button showing a tooltip.
+fn toolbar(position: Vec2) -> (Menu, Element<Button>, TestInput) {
+    let (mut menu, input) = testing::menu_with_input("Toolbar", position);
+    let save = menu.add_element(Button::new("S".to_string(), PositionType::TopLeft, Vec2::ZERO, None));
+    save.set_tooltip("Save the game");
+    (menu, save, input)
+}
+
+fn wait(menu: &mut Menu, seconds: f32) {
+    // The first frame on an element counts as no time.
+    for _ in 0..=(seconds / FRAME_TIME).ceil() as usize {
+        menu.update();
+    }
+}
+
+fn frame(menus: &[&Menu]) -> DisplayList {
+    render::record(|| {
+        for menu in menus {
+            menu.draw();
+        }
+        tooltip::draw_overlay();
+    })
+}
+
+fn tooltip_text(list: &DisplayList, shown: &str) -> Option<Vec2> {
+    list.commands.iter().find_map(|command| match command {
+        DrawCommand::Text { text, position, .. } if text == shown => Some(*position),
+        _ => None,
+    })
+}
+
+#[test]
+fn shows_after_the_delay_next_to_the_pointer() {
+    let (mut menu, save, input) = toolbar(vec2(100.0, 100.0));
+    let pointer = menu.element_rect(&save).unwrap().center();
+    input.move_mouse(pointer);
+
+    menu.update();
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_none());
+
+    wait(&mut menu, tooltip::delay());
+    let list = frame(&[&menu]);
+    let position = tooltip_text(&list, "Save the game").unwrap();
+    assert!(position.x > pointer.x && position.x < pointer.x + 30.0);
+    assert!(position.y > pointer.y && position.y < pointer.y + 50.0);
+
+    // Drawn in the overlay, after everything the menu drew.
+    assert!(tooltip_text(&testing::snapshot(&menu), "Save the game").is_none());
+    assert!(matches!(list.commands.last(), Some(DrawCommand::Text { text, .. }) if text == "Save the game"));
+}
+
+#[test]
+fn follows_the_configured_delay() {
+    let (mut menu, save, input) = toolbar(vec2(100.0, 100.0));
+    tooltip::set_delay(0.0);
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    menu.update();
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_some());
+    tooltip::set_delay(0.5);
+}
+
+#[test]
+fn hides_on_click_and_when_the_pointer_leaves() {
+    let (mut menu, save, input) = toolbar(vec2(100.0, 100.0));
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    wait(&mut menu, tooltip::delay());
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_some());
+
+    input.press();
+    menu.update();
+    input.release();
+    wait(&mut menu, tooltip::delay());
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_none());
+
+    // Left and back, it shows again after the delay.
+    input.move_mouse(vec2(0.0, 0.0));
+    menu.update();
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_none());
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    wait(&mut menu, tooltip::delay());
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_some());
+}
+
+#[test]
+fn stays_on_the_screen() {
+    let (mut menu, save, input) = toolbar(SCREEN_SIZE - vec2(30.0, 30.0));
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    wait(&mut menu, tooltip::delay());
+
+    let list = frame(&[&menu]);
+    let background = list.commands.iter().rev().find_map(|command| match command {
+        DrawCommand::Rectangle { rect, .. } => Some(*rect),
+        _ => None,
+    }).unwrap();
+    assert!(background.right() <= SCREEN_SIZE.x && background.bottom() <= SCREEN_SIZE.y);
+    assert!(background.x >= 0.0 && background.y >= 0.0);
+}
+
+#[test]
+fn widgets_can_be_tooltips() {
+    let (mut menu, save, input) = toolbar(vec2(100.0, 100.0));
+    save.set_tooltip(Tooltip::Widget(Box::new(TextLabel::new("Ctrl+S".to_string(), PositionType::TopLeft, Vec2::ZERO))));
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    wait(&mut menu, tooltip::delay());
+    assert!(tooltip_text(&frame(&[&menu]), "Ctrl+S").is_some());
+
+    save.remove_tooltip();
+    assert!(tooltip_text(&frame(&[&menu]), "Ctrl+S").is_none());
+}
+
+#[test]
+fn menus_drawn_on_top_hide_the_tooltips_below() {
+    let (mut menu, save, input) = toolbar(vec2(100.0, 100.0));
+    let (mut cover, cover_input) = testing::menu_with_input("Cover", vec2(90.0, 90.0));
+    cover.size = Some(vec2(200.0, 200.0));
+    input.move_mouse(menu.element_rect(&save).unwrap().center());
+    cover_input.move_mouse(menu.element_rect(&save).unwrap().center());
+    wait(&mut menu, tooltip::delay());
+    cover.update();
+
+    assert!(tooltip_text(&frame(&[&menu]), "Save the game").is_some());
+    assert!(tooltip_text(&frame(&[&menu, &cover]), "Save the game").is_none());
+}
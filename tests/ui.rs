@@ -0,0 +1,6 @@
+// The errors of the derives, checked against the `.stderr` files next to each case.
+#[test]
+fn derive_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
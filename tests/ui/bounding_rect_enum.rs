@@ -0,0 +1,9 @@
+use macros::BoundingRect;
+
+#[derive(BoundingRect)]
+enum Marker {
+    Shown,
+    Hidden,
+}
+
+fn main() {}
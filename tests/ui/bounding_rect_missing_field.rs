@@ -0,0 +1,10 @@
+use Simples_menu::macroquad::prelude::*;
+use macros::BoundingRect;
+
+#[derive(BoundingRect)]
+struct Marker {
+    visible: bool,
+    origin: Vec2,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use Simples_menu::macroquad::prelude::*;
+use macros::BoundingRect;
+
+#[derive(BoundingRect)]
+struct Marker(bool, Vec2, Vec2);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use Simples_menu::macroquad::prelude::*;
+use macros::BoundingRect;
+
+#[derive(BoundingRect)]
+#[bounding(origin = "position")]
+struct Marker {
+    visible: bool,
+    position: Vec2,
+    size: Vec2,
+}
+
+fn main() {}